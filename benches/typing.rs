@@ -0,0 +1,43 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use std::time::Duration;
+use terminal_typer::types::typing::Typing;
+
+const LINE: &str = "    let value = compute(first, second);";
+const SIZES: [usize; 3] = [100, 10_000, 100_000];
+
+fn started(lines: usize) -> Typing {
+	let text = vec![LINE; lines].join("\n");
+	Typing::new(&text, Duration::from_secs(60), 10)
+		.unwrap()
+		.start()
+}
+
+/// A keystroke should cost the same however long the text is.
+fn input(c: &mut Criterion) {
+	let mut group = c.benchmark_group("input");
+	for lines in SIZES {
+		let typing = started(lines);
+		group.bench_with_input(BenchmarkId::from_parameter(lines), &typing, |b, typing| {
+			b.iter_batched(
+				|| typing.clone(),
+				|typing| black_box(typing.input('l').input('x').input('e')),
+				BatchSize::SmallInput,
+			)
+		});
+	}
+	group.finish();
+}
+
+fn display_lines(c: &mut Criterion) {
+	let mut group = c.benchmark_group("display_lines");
+	for lines in SIZES {
+		let typing = started(lines);
+		group.bench_with_input(BenchmarkId::from_parameter(lines), &typing, |b, typing| {
+			b.iter(|| black_box(typing.display_lines().len()))
+		});
+	}
+	group.finish();
+}
+
+criterion_group!(benches, input, display_lines);
+criterion_main!(benches);
@@ -1,10 +1,29 @@
+use crate::config::{parse_duration, Goals};
+use crate::history::bests::Comparison;
+use crate::history::goals::DailyProgress;
+use crate::history::recording::Recording;
+use crate::history::run::Run;
+use crate::keyboard::layout::Layout;
+use crate::keyboard::stats::KeyStats;
+use crate::types::analysis::Analysis;
+use crate::types::cycle::Cycle;
+use crate::types::keystroke::Keystroke;
+use crate::types::policy::ErrorPolicy;
+use crate::types::start::StartMode;
 use crate::types::typing::Typing;
-use anyhow::Result;
-use encoding::all::ISO_8859_1;
-use encoding::{DecoderTrap, EncoderTrap, Encoding};
-use std::time::Duration;
-
-const SELECTABLE_TIME: [&'static usize; 4] = [&15, &30, &60, &120];
+use anyhow::{anyhow, Result};
+use std::cell::OnceCell;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const SELECTABLE_TIME: [u64; 4] = [15, 30, 60, 120];
+const ANALYSIS_LIMIT: usize = 5;
+const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+const BURST_WINDOW: Duration = Duration::from_secs(3);
+const COUNTDOWN: Duration = Duration::from_secs(3);
+const FLASH: Duration = Duration::from_millis(300);
+/// About 30 frames per second.
+const DEFAULT_FRAME_RATE: Duration = Duration::from_millis(33);
 
 #[derive(Clone, Debug)]
 pub struct App {
@@ -12,6 +31,29 @@ pub struct App {
 	pub typing: Typing,
 	progress: TypingProgress,
 	custom_time: Duration,
+	durations: Vec<Duration>,
+	time_input: Option<String>,
+	start_mode: StartMode,
+	countdown: Option<Instant>,
+	bests: Vec<Comparison>,
+	goals: Goals,
+	daily: Option<DailyProgress>,
+	os_layout: Layout,
+	layout: Layout,
+	show_keyboard: bool,
+	keyboard_panel: bool,
+	flash: Option<(char, Instant)>,
+	hover: Option<(u16, u16)>,
+	/// Running time when the test was paused.
+	paused: Option<Duration>,
+	tenths: bool,
+	frame_rate: Duration,
+	/// Whether the text is only the start of its file.
+	truncated: bool,
+	/// The result of the finished test, measured on the first frame that shows it.
+	result: OnceCell<TypingResult>,
+	/// The analysis of the finished test, built once rather than on every frame.
+	analysis: OnceCell<Analysis>,
 }
 
 #[derive(Clone, Debug)]
@@ -22,34 +64,81 @@ pub struct TypingResult {
 	pub typo: usize,
 	pub wpm_max: f64,
 	pub wpm_plot: Vec<(f64, f64)>,
+	pub burst_plot: Vec<(f64, f64)>,
 	pub acc_plot: Vec<(f64, f64)>,
+	pub error_plot: Vec<(f64, f64)>,
 }
 
 impl App {
 	pub fn new(text: &str, remaining_time: Duration, display_lines: usize) -> Result<App> {
 		let text = App::filter_text(text);
+		if text.trim().is_empty() {
+			return Err(anyhow!(
+				"Nothing left to type after dropping characters outside Latin-1."
+			));
+		}
 		let typing = Typing::new(&text, remaining_time, display_lines)?;
 		Ok(App {
 			typing: typing,
 			time: remaining_time,
 			custom_time: remaining_time,
-			progress: TypingProgress::new(),
+			durations: SELECTABLE_TIME.map(Duration::from_secs).to_vec(),
+			time_input: None,
+			start_mode: StartMode::default(),
+			countdown: None,
+			progress: TypingProgress::new(DEFAULT_SAMPLE_INTERVAL),
+			bests: Vec::new(),
+			goals: Goals::default(),
+			daily: None,
+			os_layout: Layout::qwerty(),
+			layout: Layout::qwerty(),
+			show_keyboard: false,
+			keyboard_panel: false,
+			flash: None,
+			hover: None,
+			paused: None,
+			tenths: false,
+			frame_rate: DEFAULT_FRAME_RATE,
+			truncated: false,
+			result: OnceCell::new(),
+			analysis: OnceCell::new(),
 		})
 	}
 
 	pub fn result(&self) -> TypingResult {
+		if self.typing.is_finish() {
+			self.result.get_or_init(|| self.measure()).clone()
+		} else {
+			self.measure()
+		}
+	}
+
+	fn measure(&self) -> TypingResult {
+		let keystrokes = self.typing.keystrokes();
+		let elapsed = keystrokes
+			.last()
+			.map(|k| Duration::from_millis(k.at))
+			.unwrap_or_default()
+			.max(self.elapsed_time());
+		let samples = self.progress.samples(keystrokes, elapsed);
 		TypingResult {
 			wpm: self.typing.wpm(),
 			acc: self.typing.acc(),
 			typed: self.typing.typed(),
 			typo: self.typing.typo(),
-			wpm_max: self.progress.wpm_max(),
-			wpm_plot: self.progress.wpm_plot(),
-			acc_plot: self.progress.acc_plot(),
+			wpm_max: samples
+				.iter()
+				.fold(1.0, |max: f64, s| max.max(s.wpm).max(s.burst))
+				.ceil(),
+			wpm_plot: samples.iter().map(|s| (s.at, s.wpm)).collect(),
+			burst_plot: samples.iter().map(|s| (s.at, s.burst)).collect(),
+			acc_plot: samples.iter().map(|s| (s.at, s.acc)).collect(),
+			error_plot: self.progress.errors(keystrokes),
 		}
 	}
 
 	pub fn start(mut self) -> Self {
+		self.countdown = None;
 		match self.typing {
 			Typing::BeforeStart(_) => {
 				let typing = self.typing.update_remaining_time(self.time);
@@ -63,6 +152,10 @@ impl App {
 	pub fn restart(mut self, text: &str) -> Self {
 		let text = App::filter_text(text);
 		self.typing = self.typing.restart(&text, self.time);
+		self.result = OnceCell::new();
+		self.analysis = OnceCell::new();
+		self.bests = Vec::new();
+		self.countdown = None;
 		self
 	}
 
@@ -72,150 +165,442 @@ impl App {
 	}
 
 	pub fn input(mut self, c: char) -> Self {
+		let c = self.layout.remap(&self.os_layout, c);
+		let typo = self.typing.typo();
 		self.typing = self.typing.input(c);
+		if self.typing.typo() > typo {
+			self.flash = Some((c, Instant::now()));
+		}
 		self
 	}
 
 	pub fn tick(mut self) -> Self {
-		self.typing = self.typing.tick();
-		self.progress = self.progress.add(self.typing.clone());
+		if self.paused.is_none() {
+			self.typing = self.typing.tick();
+		}
+		self
+	}
+
+	/// Stops the clock of a running test, as when the terminal loses focus.
+	pub fn pause(mut self) -> Self {
+		if let (Typing::Running(_), None) = (&self.typing, self.paused) {
+			self.paused = Some(self.typing.running_time());
+		}
+		self
+	}
+
+	/// Restarts the clock where `pause` stopped it.
+	pub fn resume(mut self) -> Self {
+		if let Some(elapsed) = self.paused.take() {
+			self.typing = self.typing.with_elapsed(elapsed);
+		}
+		self
+	}
+
+	pub fn is_paused(&self) -> bool {
+		self.paused.is_some()
+	}
+
+	/// Shows the remaining time in tenths of a second.
+	pub fn with_tenths(mut self, tenths: bool) -> Self {
+		self.tenths = tenths;
+		self
+	}
+
+	pub fn tenths(&self) -> bool {
+		self.tenths
+	}
+
+	/// The shortest time between two drawn frames.
+	pub fn with_frame_rate(mut self, frame_rate: Duration) -> Self {
+		self.frame_rate = frame_rate;
+		self
+	}
+
+	pub fn frame_rate(&self) -> Duration {
+		self.frame_rate
+	}
+
+	pub fn with_truncated(mut self, truncated: bool) -> Self {
+		self.truncated = truncated;
+		self
+	}
+
+	pub fn truncated(&self) -> bool {
+		self.truncated
+	}
+
+	/// The remaining time to the millisecond, kept within the second the clock shows.
+	pub fn remaining(&self) -> Duration {
+		let whole = Duration::from_secs(self.typing.get_remaining_time() as u64);
+		let elapsed = self.paused.unwrap_or_else(|| self.typing.running_time());
+		self.time
+			.saturating_sub(elapsed)
+			.clamp(whole.saturating_sub(Duration::from_secs(1)), whole)
+	}
+
+	pub fn run(&self, file: &Path) -> Run {
+		Run {
+			id: 0,
+			timestamp: Run::now(),
+			file: file.to_path_buf(),
+			extension: Run::extension_of(file),
+			duration: self.time.as_secs(),
+			elapsed: self.elapsed_time().as_secs(),
+			wpm: self.typing.wpm(),
+			acc: self.typing.acc(),
+			typed: self.typing.typed(),
+			typo: self.typing.typo(),
+			policy: self.typing.policy(),
+		}
+	}
+
+	pub fn analysis(&self) -> Analysis {
+		if self.typing.is_finish() {
+			self.analysis.get_or_init(|| self.analyze()).clone()
+		} else {
+			self.analyze()
+		}
+	}
+
+	fn analyze(&self) -> Analysis {
+		Analysis::new(
+			self.typing.keystrokes(),
+			&self.typing.lines_text(),
+			ANALYSIS_LIMIT,
+		)
+	}
+
+	pub fn recording(&self, text: &str) -> Recording {
+		Recording {
+			text: text.to_string(),
+			policy: self.typing.policy(),
+			editor: self.typing.is_editor(),
+			warm_up: self.typing.has_warm_up(),
+			keystrokes: self.typing.keystrokes().to_vec(),
+		}
+	}
+
+	pub fn with_elapsed(mut self, elapsed: Duration) -> Self {
+		self.typing = self.typing.with_elapsed(elapsed);
+		self
+	}
+
+	pub fn with_sample_interval(mut self, interval: Duration) -> Self {
+		self.progress = TypingProgress::new(interval);
+		self
+	}
+
+	/// Practices `layout` while the OS sends keys for `os_layout`.
+	pub fn with_layouts(mut self, os_layout: Layout, layout: Layout) -> Self {
+		self.os_layout = os_layout;
+		self.layout = layout;
 		self
 	}
 
+	pub fn layout(&self) -> &Layout {
+		&self.layout
+	}
+
+	pub fn key_stats(&self) -> KeyStats {
+		KeyStats::new(self.typing.keystrokes(), &self.layout)
+	}
+
+	pub fn toggle_keyboard(mut self) -> Self {
+		self.show_keyboard = !self.show_keyboard;
+		self
+	}
+
+	pub fn shows_keyboard(&self) -> bool {
+		self.show_keyboard
+	}
+
+	pub fn with_keyboard_panel(mut self, keyboard_panel: bool) -> Self {
+		self.keyboard_panel = keyboard_panel;
+		self
+	}
+
+	pub fn shows_keyboard_panel(&self) -> bool {
+		self.keyboard_panel
+	}
+
+	/// Where the mouse is, in terminal cells.
+	pub fn with_hover(mut self, hover: Option<(u16, u16)>) -> Self {
+		self.hover = hover;
+		self
+	}
+
+	pub fn hover(&self) -> Option<(u16, u16)> {
+		self.hover
+	}
+
+	/// The key that was just mistyped, while it should still be flashed.
+	pub fn flash(&self) -> Option<char> {
+		self.flash
+			.filter(|(_, at)| at.elapsed() < FLASH)
+			.map(|(c, _)| c)
+	}
+
+	pub fn with_start_mode(mut self, start_mode: StartMode) -> Self {
+		self.start_mode = start_mode;
+		self
+	}
+
+	pub fn start_mode(&self) -> StartMode {
+		self.start_mode
+	}
+
+	pub fn with_warm_up(mut self, warm_up: bool) -> Self {
+		self.typing = self.typing.with_warm_up(warm_up);
+		self
+	}
+
+	/// Moves past the warm-up line untyped, where a replay's keystrokes begin.
+	pub fn skip_warm_up(mut self) -> Self {
+		if self.typing.is_warming_up() {
+			self.typing = self.typing.next();
+		}
+		self
+	}
+
+	pub fn begin_countdown(mut self) -> Self {
+		if self.typing.is_before_start() && self.countdown.is_none() {
+			self.countdown = Some(Instant::now());
+		}
+		self
+	}
+
+	/// Whole seconds left before the countdown starts the test, rounded up.
+	pub fn countdown(&self) -> Option<u64> {
+		self.countdown
+			.map(|c| COUNTDOWN.saturating_sub(c.elapsed()).as_millis() as u64)
+			.map(|left| left.div_ceil(1000))
+	}
+
+	/// Starts the test once the countdown has run out.
+	pub fn update(self) -> Self {
+		match self.countdown() {
+			Some(0) => self.start(),
+			_ => self,
+		}
+	}
+
+	pub fn with_editor(mut self, editor: bool) -> Self {
+		self.typing = self.typing.with_editor(editor);
+		self
+	}
+
+	pub fn toggle_editor(mut self) -> Self {
+		if self.typing.is_before_start() {
+			let editor = !self.typing.is_editor();
+			self.typing = self.typing.with_editor(editor);
+		}
+		self
+	}
+
+	pub fn with_policy(mut self, policy: ErrorPolicy) -> Self {
+		self.typing = self.typing.with_policy(policy);
+		self
+	}
+
+	pub fn with_goals(mut self, goals: Goals) -> Self {
+		self.goals = goals;
+		self
+	}
+
+	pub fn goals(&self) -> Goals {
+		self.goals
+	}
+
+	pub fn with_daily(mut self, daily: DailyProgress) -> Self {
+		self.daily = Some(daily);
+		self
+	}
+
+	pub fn daily(&self) -> Option<&DailyProgress> {
+		self.daily.as_ref()
+	}
+
+	pub fn with_bests(mut self, bests: Vec<Comparison>) -> Self {
+		self.bests = bests;
+		self
+	}
+
+	pub fn bests(&self) -> &Vec<Comparison> {
+		&self.bests
+	}
+
 	pub fn selectable_time(&self) -> Vec<Duration> {
-		let mut times = [
-			SELECTABLE_TIME
-				.map(|t| Duration::from_secs(*t as u64))
-				.to_vec(),
-			vec![self.custom_time],
-		]
-		.concat();
-		times.sort();
-		times.dedup();
-		times
+		self.times().items().to_vec()
 	}
 
 	pub fn next_time(mut self) -> Self {
-		let time = self.time.as_secs();
-		let custom_time = self.custom_time.as_secs();
-		let time = Duration::from_secs(match time {
-			15 => 30,
-			30 => 60,
-			60 => 120,
-			120 => {
-				if custom_time != 15 {
-					custom_time
-				} else {
-					15
-				}
-			}
-			_ => {
-				if custom_time < 15 {
-					15
-				} else if custom_time > 120 {
-					15
-				} else {
-					custom_time
-				}
-			}
-		} as u64);
-
-		self.time = time;
+		if let Some(time) = self.times().next(&self.time) {
+			self.time = time;
+		}
 		self
 	}
 
 	pub fn prev_time(mut self) -> Self {
-		let time = self.time.as_secs();
-		let custom_time = self.custom_time.as_secs();
-		let time = Duration::from_secs(match time {
-			15 => {
-				if custom_time != 120 {
-					custom_time
-				} else {
-					120
-				}
-			}
-			30 => 15,
-			60 => 30,
-			120 => 60,
-			_ => {
-				if custom_time > 15 {
-					120
-				} else if custom_time < 120 {
-					120
-				} else {
-					custom_time
-				}
-			}
-		} as u64);
+		if let Some(time) = self.times().prev(&self.time) {
+			self.time = time;
+		}
+		self
+	}
 
-		self.time = time;
+	/// Picks one of the selectable times, as when clicking it.
+	pub fn select_time(mut self, time: Duration) -> Self {
+		if self.typing.is_before_start() && self.times().items().contains(&time) {
+			self.time = time;
+		}
+		self
+	}
+
+	pub fn with_durations(mut self, durations: Vec<Duration>) -> Self {
+		if !durations.is_empty() {
+			self.durations = durations;
+		}
+		self
+	}
+
+	pub fn start_time_input(mut self) -> Self {
+		self.time_input = Some(String::new());
+		self
+	}
+
+	pub fn time_input(&self) -> Option<&String> {
+		self.time_input.as_ref()
+	}
+
+	pub fn input_time(mut self, c: char) -> Self {
+		if let Some(input) = self.time_input.as_mut() {
+			input.push(c);
+		}
+		self
+	}
+
+	pub fn delete_time(mut self) -> Self {
+		if let Some(input) = self.time_input.as_mut() {
+			input.pop();
+		}
+		self
+	}
+
+	pub fn cancel_time_input(mut self) -> Self {
+		self.time_input = None;
+		self
+	}
+
+	/// Selects the entered duration, keeping the input open if it doesn't parse.
+	pub fn submit_time(mut self) -> Self {
+		let time = self
+			.time_input
+			.as_deref()
+			.and_then(|t| parse_duration(t).ok());
+		if let Some(time) = time {
+			self.custom_time = time;
+			self.time = time;
+			self.time_input = None;
+		}
 		self
 	}
 
+	fn times(&self) -> Cycle<Duration> {
+		Cycle::new([self.durations.clone(), vec![self.custom_time]].concat())
+	}
+
 	pub fn elapsed_time(&self) -> Duration {
 		self.time - Duration::from_secs(self.typing.get_remaining_time() as u64)
 	}
 
-	fn filter_text(text: &str) -> String {
-		let text = ISO_8859_1.encode(text, EncoderTrap::Ignore).unwrap();
-		ISO_8859_1
-			.decode(&text, DecoderTrap::Strict)
-			.unwrap()
-			.replace("\t", "    ")
-			.to_owned()
+	/// Drops chars outside Latin-1 and expands tabs, in one pass over the text.
+	pub fn filter_text(text: &str) -> String {
+		let mut filtered = String::with_capacity(text.len());
+		for c in text.chars() {
+			match c {
+				'\t' => filtered.push_str("    "),
+				c if u32::from(c) <= 0xff => filtered.push(c),
+				_ => (),
+			}
+		}
+		filtered
 	}
 }
 
 #[derive(Clone, Debug)]
 struct TypingProgress {
-	wpm: Vec<usize>,
-	acc: Vec<usize>,
+	interval: Duration,
 }
 
-impl TypingProgress {
-	pub fn new() -> Self {
-		TypingProgress {
-			wpm: Vec::new(),
-			acc: Vec::new(),
-		}
-	}
-
-	pub fn add(mut self, typing: Typing) -> Self {
-		self.wpm.push(typing.wpm());
-		self.acc.push(typing.acc());
-		self
-	}
+#[derive(Clone, Debug, PartialEq)]
+struct Sample {
+	at: f64,
+	wpm: f64,
+	burst: f64,
+	acc: f64,
+}
 
-	pub fn wpm_max(&self) -> f64 {
-		self.wpm.iter().fold(usize::MIN, |a, b| a.max(*b)) as f64
+impl TypingProgress {
+	pub fn new(interval: Duration) -> Self {
+		TypingProgress { interval }
 	}
 
-	pub fn wpm_plot(&self) -> Vec<(f64, f64)> {
-		let mut wpm: Vec<(f64, f64)> = self
-			.wpm
-			.iter()
-			.enumerate()
-			.map(|(i, wpm)| (i as f64, *wpm as f64))
+	/// Samples cumulative wpm, burst wpm over the last `BURST_WINDOW` and accuracy
+	/// every `interval` from the start of the test up to `elapsed`, in one sweep over
+	/// the keystrokes, which are in the order they were typed.
+	fn samples(&self, keystrokes: &[Keystroke], elapsed: Duration) -> Vec<Sample> {
+		let interval = self.interval.as_millis().max(1) as u64;
+		let elapsed = elapsed.as_millis() as u64;
+		let mut times: Vec<u64> = (1..)
+			.map(|i| i * interval)
+			.take_while(|t| *t < elapsed)
 			.collect();
-
-		wpm.insert(0, (0.0, 0.0));
-		wpm
+		times.push(elapsed);
+
+		let mut samples = vec![Sample {
+			at: 0.0,
+			wpm: 0.0,
+			burst: 0.0,
+			acc: 100.0,
+		}];
+		let window = BURST_WINDOW.as_millis() as u64;
+		let (mut typed, mut burst_start, mut correct) = (0, 0, 0);
+		samples.extend(times.into_iter().filter(|t| *t > 0).map(|t| {
+			while typed < keystrokes.len() && keystrokes[typed].at <= t {
+				correct += keystrokes[typed].correct as usize;
+				typed += 1;
+			}
+			while burst_start < typed && keystrokes[burst_start].at + window <= t {
+				burst_start += 1;
+			}
+			Sample {
+				at: t as f64 / 1000.0,
+				wpm: wpm(typed, t),
+				burst: wpm(typed - burst_start, window.min(t)),
+				acc: if typed == 0 {
+					100.0
+				} else {
+					correct as f64 / typed as f64 * 100.0
+				},
+			}
+		}));
+		samples
 	}
 
-	pub fn acc_plot(&self) -> Vec<(f64, f64)> {
-		let mut acc: Vec<(f64, f64)> = self
-			.acc
+	fn errors(&self, keystrokes: &[Keystroke]) -> Vec<(f64, f64)> {
+		keystrokes
 			.iter()
 			.enumerate()
-			.map(|(i, acc)| (i as f64, *acc as f64))
-			.collect();
-
-		acc.insert(0, (0.0, 100.0));
-		acc
+			.filter(|(_, k)| !k.correct)
+			.map(|(i, k)| (k.at as f64 / 1000.0, wpm(i + 1, k.at)))
+			.collect()
 	}
 }
 
+fn wpm(keys: usize, millis: u64) -> f64 {
+	keys as f64 * 12_000.0 / millis.max(1) as f64
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -262,6 +647,69 @@ mod tests {
 		assert_eq!(app.start().tick().elapsed_time(), Duration::from_secs(1));
 	}
 
+	#[test]
+	fn run() {
+		let app = App::new("test", Duration::from_secs(10), 10).unwrap();
+		let run = app
+			.start()
+			.input('t')
+			.tick()
+			.finish()
+			.run(Path::new("src/main.rs"));
+		assert_eq!(run.extension, "rs");
+		assert_eq!(run.duration, 10);
+		assert_eq!(run.elapsed, 1);
+		assert_eq!(run.typed, 1);
+	}
+
+	#[test]
+	fn nothing_to_type() {
+		assert!(App::new("\u{4e2d}\u{6587}", Duration::from_secs(10), 10).is_err());
+		assert!(App::new("a\u{4e2d}", Duration::from_secs(10), 10).is_ok());
+	}
+
+	#[test]
+	fn filter_text() {
+		assert_eq!(App::filter_text("\tcaf\u{e9}\u{4e2d}!"), "    caf\u{e9}!");
+	}
+
+	#[test]
+	fn pause() {
+		let app = App::new("test", Duration::from_secs(10), 10).unwrap();
+		assert!(!app.pause().is_paused());
+
+		let app = App::new("test", Duration::from_secs(10), 10)
+			.unwrap()
+			.start()
+			.pause();
+		assert!(app.is_paused());
+		assert_eq!(app.clone().tick().typing.get_remaining_time(), 10);
+
+		let app = app.resume();
+		assert!(!app.is_paused());
+		assert_eq!(app.tick().typing.get_remaining_time(), 9);
+	}
+
+	#[test]
+	fn remaining() {
+		let app = App::new("test", Duration::from_secs(10), 10).unwrap();
+		assert_eq!(app.remaining(), Duration::from_secs(10));
+
+		let app = app.start().tick();
+		assert!(app.remaining() <= Duration::from_secs(9));
+		assert!(app.remaining() >= Duration::from_secs(8));
+	}
+
+	#[test]
+	fn select_time() {
+		let app = App::new("test", Duration::from_secs(10), 10).unwrap();
+		let app = app.select_time(Duration::from_secs(60));
+		assert_eq!(app.time, Duration::from_secs(60));
+
+		let app = app.select_time(Duration::from_secs(45));
+		assert_eq!(app.time, Duration::from_secs(60));
+	}
+
 	#[test]
 	fn next_time_less_then_15() {
 		let app = App::new("test", Duration::from_secs(10), 10).unwrap();
@@ -337,4 +785,175 @@ mod tests {
 		let app = app.prev_time();
 		assert_eq!(app.time, Duration::from_secs(240));
 	}
+
+	fn key(at: u64, correct: bool) -> Keystroke {
+		Keystroke {
+			at,
+			key: 'x',
+			expected: Some('x'),
+			correct,
+			line_no: 1,
+			column: 0,
+		}
+	}
+
+	#[test]
+	fn samples() {
+		let keystrokes: Vec<Keystroke> = (1..=10).map(|i| key(i * 500, i != 4)).collect();
+		let samples = TypingProgress::new(Duration::from_millis(2500))
+			.samples(&keystrokes, Duration::from_secs(5));
+		assert_eq!(samples.len(), 3);
+		assert_eq!(samples[1].at, 2.5);
+		assert_eq!(samples[1].wpm, 24.0);
+		assert_eq!(samples[1].acc, 80.0);
+		assert_eq!(samples[2].burst, 24.0);
+
+		let errors = TypingProgress::new(Duration::from_secs(1)).errors(&keystrokes);
+		assert_eq!(errors, vec![(2.0, 24.0)]);
+
+		let samples = TypingProgress::new(Duration::from_millis(1))
+			.samples(&keystrokes, Duration::from_secs(5));
+		assert_eq!(samples.len(), 5001);
+		assert_eq!(samples[3500].burst, 24.0);
+		assert_eq!(samples[5000].acc, 90.0);
+	}
+
+	#[test]
+	fn result_once_finished() {
+		let app = App::new("ab", Duration::from_secs(10), 10)
+			.unwrap()
+			.start()
+			.input('a');
+		assert_eq!(app.result().typed, 1);
+
+		let app = app.finish();
+		let result = app.result();
+		assert_eq!(app.result().wpm_plot, result.wpm_plot);
+		assert!(app.restart("ab").result.get().is_none());
+	}
+
+	#[test]
+	fn replay_warm_up() {
+		let text = "ab\ncd ef";
+		let app = "abcdx ef"
+			.chars()
+			.fold(
+				App::new(text, Duration::from_secs(10), 10)
+					.unwrap()
+					.with_warm_up(true)
+					.start(),
+				|app, c| app.input(c),
+			)
+			.finish();
+		let recording = app.recording(text);
+		assert!(recording.warm_up);
+
+		let replay = recording
+			.keystrokes
+			.iter()
+			.fold(
+				App::new(&recording.text, Duration::from_secs(10), 10)
+					.unwrap()
+					.with_warm_up(recording.warm_up)
+					.start()
+					.skip_warm_up(),
+				|app, k| app.input(k.key),
+			)
+			.finish();
+		assert_eq!(
+			(replay.typing.typed(), replay.typing.typo()),
+			(app.typing.typed(), app.typing.typo())
+		);
+		assert_eq!(app.typing.typo(), 1);
+		assert_eq!(
+			replay.typing.keystrokes().len(),
+			app.typing.keystrokes().len()
+		);
+	}
+
+	#[test]
+	fn analysis_once_finished() {
+		let app = App::new("ab cd", Duration::from_secs(10), 10)
+			.unwrap()
+			.start()
+			.input('a')
+			.input('b')
+			.finish();
+		let analysis = app.analysis();
+		assert_eq!(app.analysis.get(), Some(&analysis));
+		assert!(app.restart("ab").analysis.get().is_none());
+	}
+
+	#[test]
+	fn custom_durations() {
+		let app = App::new("test", Duration::from_secs(30), 10)
+			.unwrap()
+			.with_durations(vec![Duration::from_secs(300), Duration::from_secs(45)]);
+		assert_eq!(
+			app.selectable_time(),
+			vec![
+				Duration::from_secs(30),
+				Duration::from_secs(45),
+				Duration::from_secs(300)
+			]
+		);
+		assert_eq!(app.prev_time().time, Duration::from_secs(300));
+	}
+
+	#[test]
+	fn time_input() {
+		let app = App::new("test", Duration::from_secs(30), 10).unwrap();
+		let app = "5x"
+			.chars()
+			.fold(app.start_time_input(), |a, c| a.input_time(c));
+		let app = app.submit_time();
+		assert_eq!(app.time_input().unwrap(), "5x");
+
+		let app = app.delete_time().input_time('m').submit_time();
+		assert!(app.time_input().is_none());
+		assert_eq!(app.time, Duration::from_secs(300));
+		assert_eq!(app.selectable_time().len(), 5);
+		assert!(app.typing.is_before_start());
+	}
+
+	#[test]
+	fn countdown() {
+		let app = App::new("test", Duration::from_secs(10), 10)
+			.unwrap()
+			.begin_countdown();
+		assert_eq!(app.countdown(), Some(3));
+		assert!(app.clone().update().typing.is_before_start());
+
+		let mut app = app;
+		app.countdown = Instant::now().checked_sub(COUNTDOWN);
+		let app = app.update();
+		assert!(!app.typing.is_before_start());
+		assert_eq!(app.countdown(), None);
+	}
+
+	#[test]
+	fn layouts() {
+		let app = App::new("hello", Duration::from_secs(10), 10)
+			.unwrap()
+			.with_layouts(Layout::qwerty(), Layout::load("dvorak").unwrap())
+			.start()
+			.input('j')
+			.input('d');
+		assert_eq!(app.typing.typed(), 2);
+		assert_eq!(app.typing.typo(), 0);
+		assert_eq!(app.key_stats().total, 2);
+	}
+
+	#[test]
+	fn flash() {
+		let app = App::new("hello", Duration::from_secs(10), 10)
+			.unwrap()
+			.start()
+			.input('h');
+		assert_eq!(app.flash(), None);
+
+		let app = app.input('x');
+		assert_eq!(app.flash(), Some('x'));
+		assert_eq!(app.typing.current_text(), Some('e'));
+	}
 }
@@ -0,0 +1,156 @@
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const APP_DIR: &str = "terminal-typer";
+const CONFIG_FILE: &str = "config.toml";
+
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+	pub goals: Goals,
+	/// Durations offered on the start screen, as seconds or strings like "90s", "5m" or "1m30s".
+	durations: Vec<DurationValue>,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+enum DurationValue {
+	Seconds(u64),
+	Text(String),
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Goals {
+	pub minutes_per_day: Option<u64>,
+	pub runs_per_day: Option<usize>,
+	pub target_wpm: Option<usize>,
+}
+
+impl Config {
+	pub fn parse(text: &str) -> Result<Self> {
+		Ok(toml::from_str(text)?)
+	}
+
+	/// Reads `config.toml` from the user's config directory, falling back to defaults if there is none.
+	pub fn load() -> Result<Self> {
+		match Config::path() {
+			Some(path) if path.exists() => Config::parse(&fs::read_to_string(&path)?)
+				.map_err(|e| anyhow!("Invalid config {}: {}", path.display(), e)),
+			_ => Ok(Config::default()),
+		}
+	}
+
+	pub fn durations(&self) -> Result<Vec<Duration>> {
+		self.durations
+			.iter()
+			.map(|d| match d {
+				DurationValue::Seconds(s) if *s > 0 => Ok(Duration::from_secs(*s)),
+				DurationValue::Seconds(_) => Err(anyhow!("Durations must be longer than 0s.")),
+				DurationValue::Text(t) => parse_duration(t),
+			})
+			.collect()
+	}
+
+	pub fn path() -> Option<PathBuf> {
+		dirs::config_dir().map(|d| d.join(APP_DIR).join(CONFIG_FILE))
+	}
+}
+
+/// Parses "90", "90s", "5m", "1h" or combinations like "1m30s"; a bare number is seconds.
+pub fn parse_duration(text: &str) -> Result<Duration> {
+	let error = || anyhow!("Invalid duration {}, expected e.g. 90, 90s or 5m.", text);
+	let mut total: u64 = 0;
+	let mut number = String::new();
+	for c in text.trim().chars() {
+		match c {
+			'0'..='9' => number.push(c),
+			'h' | 'm' | 's' if !number.is_empty() => {
+				let unit = match c {
+					'h' => 3600,
+					'm' => 60,
+					_ => 1,
+				};
+				let secs = number.parse::<u64>().map_err(|_| error())?;
+				total = secs
+					.checked_mul(unit)
+					.and_then(|secs| total.checked_add(secs))
+					.ok_or_else(error)?;
+				number.clear();
+			}
+			_ => return Err(error()),
+		}
+	}
+	if !number.is_empty() {
+		let secs = number.parse::<u64>().map_err(|_| error())?;
+		total = total.checked_add(secs).ok_or_else(error)?;
+	}
+
+	if total == 0 {
+		Err(error())
+	} else {
+		Ok(Duration::from_secs(total))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse() {
+		let config = Config::parse("[goals]\nminutes_per_day = 15\ntarget_wpm = 60\n").unwrap();
+		assert_eq!(
+			config.goals,
+			Goals {
+				minutes_per_day: Some(15),
+				runs_per_day: None,
+				target_wpm: Some(60),
+			}
+		);
+		assert_eq!(Config::parse("").unwrap(), Config::default());
+		assert!(Config::parse("[goals]\nminutes = 15\n").is_err());
+	}
+
+	#[test]
+	fn durations() {
+		let config = Config::parse("durations = [15, \"90s\", \"5m\"]\n").unwrap();
+		assert_eq!(
+			config.durations().unwrap(),
+			vec![
+				Duration::from_secs(15),
+				Duration::from_secs(90),
+				Duration::from_secs(300)
+			]
+		);
+		assert!(Config::parse("durations = [\"soon\"]\n")
+			.unwrap()
+			.durations()
+			.is_err());
+	}
+
+	#[test]
+	fn parse_duration() {
+		assert_eq!(
+			super::parse_duration("90").unwrap(),
+			Duration::from_secs(90)
+		);
+		assert_eq!(
+			super::parse_duration("5m").unwrap(),
+			Duration::from_secs(300)
+		);
+		assert_eq!(
+			super::parse_duration("1m30s").unwrap(),
+			Duration::from_secs(90)
+		);
+		assert!(super::parse_duration("").is_err());
+		assert!(super::parse_duration("m").is_err());
+		assert!(super::parse_duration("0").is_err());
+		assert!(super::parse_duration("5x").is_err());
+		assert!(super::parse_duration("9999999999999999h").is_err());
+		assert!(super::parse_duration("18446744073709551615s1s").is_err());
+	}
+}
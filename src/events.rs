@@ -0,0 +1,84 @@
+use crossbeam_channel::{self as channel, select, Receiver, Sender};
+use crossterm::event::{self, Event};
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How long the input thread waits for a terminal event before checking whether to stop.
+const INPUT_POLL: Duration = Duration::from_millis(50);
+
+pub enum AppEvent {
+	/// A key, mouse, focus or resize event from the terminal.
+	Input(Event),
+	/// Time to advance clocks and animations.
+	Tick,
+	/// Time to draw a frame, if anything changed since the last one.
+	Render,
+}
+
+/// Terminal input, ticks and frames, each on its own channel. Input is read on a
+/// thread that stops when this is dropped.
+pub struct Events {
+	input: Receiver<io::Result<Event>>,
+	tick: Receiver<Instant>,
+	render: Receiver<Instant>,
+	stop: Arc<AtomicBool>,
+	thread: Option<JoinHandle<()>>,
+}
+
+impl Events {
+	pub fn new(tick_rate: Duration, frame_rate: Duration) -> Self {
+		let (tx, input) = channel::unbounded();
+		let stop = Arc::new(AtomicBool::new(false));
+		let thread = Events::input(tx, stop.clone());
+		Events {
+			input,
+			tick: channel::tick(tick_rate),
+			render: channel::tick(frame_rate),
+			stop,
+			thread: Some(thread),
+		}
+	}
+
+	/// Waits for the next event, taking pending input before ticks and frames.
+	pub fn next(&self) -> io::Result<AppEvent> {
+		if let Ok(event) = self.input.try_recv() {
+			return event.map(AppEvent::Input);
+		}
+		select! {
+			recv(self.input) -> event => event.map_err(io::Error::other)?.map(AppEvent::Input),
+			recv(self.tick) -> _ => Ok(AppEvent::Tick),
+			recv(self.render) -> _ => Ok(AppEvent::Render),
+		}
+	}
+
+	fn input(tx: Sender<io::Result<Event>>, stop: Arc<AtomicBool>) -> JoinHandle<()> {
+		thread::spawn(move || loop {
+			let ready = event::poll(INPUT_POLL);
+			// Once stopped, a waiting event is left for whoever reads the terminal next.
+			if stop.load(Ordering::Relaxed) {
+				break;
+			}
+			let event = match ready {
+				Ok(true) => event::read(),
+				Ok(false) => continue,
+				Err(err) => Err(err),
+			};
+			let failed = event.is_err();
+			if tx.send(event).is_err() || failed {
+				break;
+			}
+		})
+	}
+}
+
+impl Drop for Events {
+	fn drop(&mut self) {
+		self.stop.store(true, Ordering::Relaxed);
+		if let Some(thread) = self.thread.take() {
+			let _ = thread.join();
+		}
+	}
+}
@@ -0,0 +1,6 @@
+pub mod bests;
+pub mod goals;
+pub mod recording;
+pub mod run;
+pub mod stats;
+pub mod store;
@@ -0,0 +1,201 @@
+use crate::history::run::Run;
+use crate::types::policy::ErrorPolicy;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Category {
+	Extension(String),
+	File(PathBuf),
+	Duration(u64),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+	pub category: Category,
+	pub best: Option<usize>,
+	pub wpm: usize,
+}
+
+pub struct Bests {
+	runs: Vec<Run>,
+}
+
+impl Category {
+	pub fn of(run: &Run) -> Vec<Category> {
+		vec![
+			Category::Extension(run.extension.clone()),
+			Category::File(run.file.clone()),
+			Category::Duration(run.duration),
+		]
+	}
+
+	pub fn matches(&self, run: &Run) -> bool {
+		match self {
+			Category::Extension(e) => run.extension == *e,
+			Category::File(f) => run.file == *f,
+			Category::Duration(d) => run.duration == *d,
+		}
+	}
+
+	pub fn label(&self) -> String {
+		match self {
+			Category::Extension(e) => format!(".{}", e),
+			Category::File(f) => f
+				.file_name()
+				.map(|f| f.to_string_lossy().to_string())
+				.unwrap_or_else(|| f.to_string_lossy().to_string()),
+			Category::Duration(d) => format!("{}s", d),
+		}
+	}
+}
+
+impl Comparison {
+	pub fn delta(&self) -> Option<i64> {
+		self.best.map(|b| self.wpm as i64 - b as i64)
+	}
+
+	pub fn is_record(&self) -> bool {
+		self.best.map(|b| self.wpm > b).unwrap_or(false)
+	}
+}
+
+impl Bests {
+	pub fn new(runs: Vec<Run>) -> Self {
+		Bests { runs }
+	}
+
+	/// The best run in `category`; runs under another error policy aren't comparable.
+	pub fn best(&self, category: &Category, policy: ErrorPolicy) -> Option<&Run> {
+		self.runs
+			.iter()
+			.filter(|r| r.policy == policy && category.matches(r))
+			.max_by_key(|r| (r.wpm, r.acc))
+	}
+
+	pub fn compare(&self, run: &Run) -> Vec<Comparison> {
+		Category::of(run)
+			.into_iter()
+			.map(|category| Comparison {
+				best: self.best(&category, run.policy).map(|r| r.wpm),
+				category,
+				wpm: run.wpm,
+			})
+			.collect()
+	}
+
+	/// The top runs per extension and error policy.
+	pub fn leaderboard(&self, limit: usize) -> BTreeMap<(String, ErrorPolicy), Vec<Run>> {
+		let mut board: BTreeMap<(String, ErrorPolicy), Vec<Run>> = BTreeMap::new();
+		for run in self.runs.iter() {
+			board
+				.entry((run.extension.clone(), run.policy))
+				.or_default()
+				.push(run.clone());
+		}
+		for runs in board.values_mut() {
+			runs.sort_by_key(|r| Reverse((r.wpm, r.acc)));
+			runs.truncate(limit);
+		}
+		board
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn run(file: &str, duration: u64, wpm: usize) -> Run {
+		Run {
+			duration,
+			elapsed: duration,
+			wpm,
+			..Run::sample(file)
+		}
+	}
+
+	#[test]
+	fn best() {
+		let bests = Bests::new(vec![
+			run("a.rs", 30, 40),
+			run("b.rs", 60, 55),
+			run("c.go", 30, 70),
+		]);
+		assert_eq!(
+			bests
+				.best(
+					&Category::Extension("rs".to_string()),
+					ErrorPolicy::default()
+				)
+				.unwrap()
+				.wpm,
+			55
+		);
+		assert_eq!(
+			bests
+				.best(&Category::Duration(30), ErrorPolicy::default())
+				.unwrap()
+				.wpm,
+			70
+		);
+		assert!(bests
+			.best(
+				&Category::Extension("py".to_string()),
+				ErrorPolicy::default()
+			)
+			.is_none());
+	}
+
+	#[test]
+	fn compare() {
+		let bests = Bests::new(vec![run("a.rs", 30, 40), run("b.rs", 60, 55)]);
+		let comparisons = bests.compare(&run("a.rs", 30, 50));
+
+		assert_eq!(comparisons.len(), 3);
+		assert_eq!(comparisons[0].delta(), Some(-5));
+		assert!(!comparisons[0].is_record());
+		assert_eq!(comparisons[1].delta(), Some(10));
+		assert!(comparisons[1].is_record());
+	}
+
+	#[test]
+	fn first_run_is_not_a_record() {
+		let comparisons = Bests::new(vec![]).compare(&run("a.rs", 30, 50));
+		assert!(comparisons
+			.iter()
+			.all(|c| !c.is_record() && c.delta().is_none()));
+	}
+
+	#[test]
+	fn leaderboard() {
+		let bests = Bests::new(vec![
+			run("a.rs", 30, 40),
+			run("b.rs", 60, 55),
+			run("c.rs", 60, 45),
+			run("c.go", 30, 70),
+		]);
+		let board = bests.leaderboard(2);
+
+		assert_eq!(board.len(), 2);
+		assert_eq!(
+			board[&("rs".to_string(), ErrorPolicy::default())]
+				.iter()
+				.map(|r| r.wpm)
+				.collect::<Vec<usize>>(),
+			vec![55, 45]
+		);
+	}
+
+	#[test]
+	fn policies_ranked_apart() {
+		let sudden_death = Run {
+			policy: ErrorPolicy::SuddenDeath,
+			..run("a.rs", 30, 90)
+		};
+		let bests = Bests::new(vec![run("a.rs", 30, 40), sudden_death.clone()]);
+		assert_eq!(bests.compare(&run("a.rs", 30, 50))[0].delta(), Some(10));
+		assert_eq!(bests.compare(&sudden_death)[0].best, Some(90));
+		assert_eq!(bests.leaderboard(5).len(), 2);
+	}
+}
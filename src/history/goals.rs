@@ -0,0 +1,116 @@
+use crate::config::Goals;
+use crate::history::run::Run;
+use crate::history::stats::streaks;
+use chrono::{NaiveDate, TimeZone};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DayTotals {
+	/// Seconds spent typing.
+	pub seconds: u64,
+	pub runs: usize,
+	pub best_wpm: usize,
+}
+
+impl DayTotals {
+	/// Whether every configured goal is reached. Without goals any practice counts.
+	pub fn meets(&self, goals: &Goals) -> bool {
+		self.runs > 0
+			&& goals
+				.minutes_per_day
+				.map(|m| self.seconds >= m * 60)
+				.unwrap_or(true)
+			&& goals.runs_per_day.map(|r| self.runs >= r).unwrap_or(true)
+			&& goals.target_wpm.map(|w| self.best_wpm >= w).unwrap_or(true)
+	}
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DailyProgress {
+	pub goals: Goals,
+	pub today: DayTotals,
+	/// Consecutive days, up to today or yesterday, on which the goals were met.
+	pub streak: usize,
+}
+
+impl DailyProgress {
+	pub fn new<Tz: TimeZone>(runs: &[Run], goals: Goals, tz: &Tz, today: NaiveDate) -> Self {
+		let mut days: BTreeMap<NaiveDate, DayTotals> = BTreeMap::new();
+		for run in runs {
+			if let Some(time) = tz.timestamp_opt(run.timestamp as i64, 0).single() {
+				let day = days.entry(time.naive_local().date()).or_default();
+				day.seconds += run.elapsed;
+				day.runs += 1;
+				day.best_wpm = day.best_wpm.max(run.wpm);
+			}
+		}
+
+		let met: BTreeSet<NaiveDate> = days
+			.iter()
+			.filter(|(_, totals)| totals.meets(&goals))
+			.map(|(date, _)| *date)
+			.collect();
+		DailyProgress {
+			goals,
+			today: days.get(&today).cloned().unwrap_or_default(),
+			streak: streaks(&met, today).0,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::Utc;
+
+	fn run(day: u32, elapsed: u64, wpm: usize) -> Run {
+		Run {
+			timestamp: NaiveDate::from_ymd_opt(2024, 1, day)
+				.unwrap()
+				.and_hms_opt(12, 0, 0)
+				.unwrap()
+				.and_utc()
+				.timestamp() as u64,
+			duration: elapsed,
+			elapsed,
+			wpm,
+			..Run::sample("main.rs")
+		}
+	}
+
+	#[test]
+	fn progress() {
+		let goals = Goals {
+			minutes_per_day: Some(1),
+			runs_per_day: None,
+			target_wpm: Some(50),
+		};
+		let runs = vec![
+			run(1, 60, 50),
+			run(2, 30, 60),
+			run(2, 30, 40),
+			run(3, 60, 40),
+			run(4, 30, 70),
+		];
+		let today = NaiveDate::from_ymd_opt(2024, 1, 4).unwrap();
+		let progress = DailyProgress::new(&runs, goals, &Utc, today);
+		assert_eq!(
+			progress.today,
+			DayTotals {
+				seconds: 30,
+				runs: 1,
+				best_wpm: 70
+			}
+		);
+		assert!(!progress.today.meets(&goals));
+		// Day 3 missed the wpm target, which broke the streak.
+		assert_eq!(progress.streak, 0);
+
+		let progress = DailyProgress::new(&runs[..3], goals, &Utc, today.pred_opt().unwrap());
+		assert_eq!(progress.streak, 2);
+		assert_eq!(
+			DailyProgress::new(&runs, Goals::default(), &Utc, today).streak,
+			4
+		);
+	}
+}
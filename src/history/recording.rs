@@ -0,0 +1,16 @@
+use crate::types::keystroke::Keystroke;
+use crate::types::policy::ErrorPolicy;
+use serde::{Deserialize, Serialize};
+
+/// Everything needed to play a run back: the text as it was typed and every key pressed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Recording {
+	pub text: String,
+	pub policy: ErrorPolicy,
+	#[serde(default)]
+	pub editor: bool,
+	/// The first line was a warm-up, so the keystrokes start on the second.
+	#[serde(default)]
+	pub warm_up: bool,
+	pub keystrokes: Vec<Keystroke>,
+}
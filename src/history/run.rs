@@ -0,0 +1,58 @@
+use crate::types::policy::ErrorPolicy;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+	pub id: u64,
+	pub timestamp: u64,
+	pub file: PathBuf,
+	pub extension: String,
+	pub duration: u64,
+	pub elapsed: u64,
+	pub wpm: usize,
+	pub acc: usize,
+	pub typed: usize,
+	pub typo: usize,
+	/// Runs recorded before policies were stored count as the default.
+	#[serde(default)]
+	pub policy: ErrorPolicy,
+}
+
+impl Run {
+	pub fn extension_of(file: &Path) -> String {
+		file.extension()
+			.and_then(|e| e.to_str())
+			.unwrap_or("")
+			.to_lowercase()
+	}
+
+	pub fn now() -> u64 {
+		SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|d| d.as_secs())
+			.unwrap_or(0)
+	}
+}
+
+#[cfg(test)]
+impl Run {
+	/// A 30 second run on `file` at time 0, for tests to override with struct update syntax.
+	pub fn sample(file: &str) -> Self {
+		let file = PathBuf::from(file);
+		Run {
+			id: 0,
+			timestamp: 0,
+			extension: Run::extension_of(&file),
+			file,
+			duration: 30,
+			elapsed: 30,
+			wpm: 0,
+			acc: 100,
+			typed: 0,
+			typo: 0,
+			policy: ErrorPolicy::default(),
+		}
+	}
+}
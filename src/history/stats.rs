@@ -0,0 +1,257 @@
+use crate::history::run::Run;
+use chrono::{Datelike, Duration, NaiveDate, TimeZone, Timelike};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Period {
+	Day,
+	Week,
+}
+
+impl Period {
+	pub fn toggle(&self) -> Self {
+		match self {
+			Period::Day => Period::Week,
+			Period::Week => Period::Day,
+		}
+	}
+
+	pub fn label(&self) -> &'static str {
+		match self {
+			Period::Day => "daily",
+			Period::Week => "weekly",
+		}
+	}
+
+	/// How many periods after `first` the period starting on `start` is.
+	pub fn offset(&self, first: NaiveDate, start: NaiveDate) -> f64 {
+		let days = (start - first).num_days() as f64;
+		match self {
+			Period::Day => days,
+			Period::Week => days / 7.0,
+		}
+	}
+
+	/// The first day of the period `date` falls in, weeks starting on Monday.
+	fn start(&self, date: NaiveDate) -> NaiveDate {
+		match self {
+			Period::Day => date,
+			Period::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeriodAverage {
+	pub start: NaiveDate,
+	pub wpm: f64,
+	pub acc: f64,
+	pub runs: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+	pub runs: usize,
+	/// Seconds spent typing across all runs.
+	pub total_time: u64,
+	pub current_streak: usize,
+	pub longest_streak: usize,
+	pub periods: Vec<PeriodAverage>,
+	pub by_extension: Vec<(String, u64)>,
+	pub by_time_of_day: Vec<(String, u64)>,
+	pub by_duration: Vec<(String, u64)>,
+}
+
+const TIMES_OF_DAY: [(&str, u32); 4] = [
+	("night", 0),
+	("morning", 6),
+	("afternoon", 12),
+	("evening", 18),
+];
+
+impl Stats {
+	pub fn new<Tz: TimeZone>(runs: &[Run], tz: &Tz, period: Period, today: NaiveDate) -> Self {
+		let mut periods: BTreeMap<NaiveDate, Vec<&Run>> = BTreeMap::new();
+		let mut by_time_of_day: BTreeMap<usize, Vec<&Run>> = BTreeMap::new();
+		for run in runs {
+			if let Some(time) = tz.timestamp_opt(run.timestamp as i64, 0).single() {
+				let time = time.naive_local();
+				periods
+					.entry(period.start(time.date()))
+					.or_default()
+					.push(run);
+				let slot = TIMES_OF_DAY
+					.iter()
+					.rposition(|(_, hour)| time.hour() >= *hour)
+					.unwrap_or(0);
+				by_time_of_day.entry(slot).or_default().push(run);
+			}
+		}
+
+		let mut by_extension: BTreeMap<String, Vec<&Run>> = BTreeMap::new();
+		let mut by_duration: BTreeMap<u64, Vec<&Run>> = BTreeMap::new();
+		for run in runs {
+			by_extension
+				.entry(run.extension.clone())
+				.or_default()
+				.push(run);
+			by_duration.entry(run.duration).or_default().push(run);
+		}
+
+		let (current_streak, longest_streak) = streaks(&practice_days(runs, tz), today);
+		Stats {
+			runs: runs.len(),
+			total_time: runs.iter().map(|r| r.elapsed).sum(),
+			current_streak,
+			longest_streak,
+			periods: periods
+				.into_iter()
+				.map(|(start, runs)| PeriodAverage {
+					start,
+					wpm: average(&runs, |r| r.wpm),
+					acc: average(&runs, |r| r.acc),
+					runs: runs.len(),
+				})
+				.collect(),
+			by_extension: by_extension
+				.into_iter()
+				.map(|(e, runs)| (format!(".{}", e), average(&runs, |r| r.wpm).round() as u64))
+				.collect(),
+			by_time_of_day: by_time_of_day
+				.into_iter()
+				.map(|(slot, runs)| {
+					(
+						TIMES_OF_DAY[slot].0.to_string(),
+						average(&runs, |r| r.wpm).round() as u64,
+					)
+				})
+				.collect(),
+			by_duration: by_duration
+				.into_iter()
+				.map(|(d, runs)| (format!("{}s", d), average(&runs, |r| r.wpm).round() as u64))
+				.collect(),
+		}
+	}
+}
+
+/// The local dates on which at least one run was recorded.
+pub fn practice_days<Tz: TimeZone>(runs: &[Run], tz: &Tz) -> BTreeSet<NaiveDate> {
+	runs.iter()
+		.filter_map(|r| tz.timestamp_opt(r.timestamp as i64, 0).single())
+		.map(|t| t.naive_local().date())
+		.collect()
+}
+
+/// Current and longest run of consecutive practice days. The current streak is
+/// still alive if the last practice was yesterday.
+pub fn streaks(days: &BTreeSet<NaiveDate>, today: NaiveDate) -> (usize, usize) {
+	let mut longest = 0;
+	let mut length = 0;
+	let mut prev: Option<NaiveDate> = None;
+	for day in days {
+		length = match prev {
+			Some(p) if *day - p == Duration::days(1) => length + 1,
+			_ => 1,
+		};
+		longest = longest.max(length);
+		prev = Some(*day);
+	}
+
+	let current = match prev {
+		Some(last) if today - last <= Duration::days(1) && last <= today => length,
+		_ => 0,
+	};
+	(current, longest)
+}
+
+fn average<F: Fn(&Run) -> usize>(runs: &[&Run], f: F) -> f64 {
+	if runs.is_empty() {
+		0.0
+	} else {
+		runs.iter().map(|r| f(r) as f64).sum::<f64>() / runs.len() as f64
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::Utc;
+
+	fn run(date: &str, hour: u32, extension: &str, wpm: usize) -> Run {
+		let time = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+			.unwrap()
+			.and_hms_opt(hour, 0, 0)
+			.unwrap();
+		Run {
+			timestamp: time.and_utc().timestamp() as u64,
+			extension: extension.to_string(),
+			wpm,
+			acc: 90,
+			..Run::sample("main.rs")
+		}
+	}
+
+	fn date(date: &str) -> NaiveDate {
+		NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+	}
+
+	#[test]
+	fn streaks() {
+		let days: BTreeSet<NaiveDate> = [
+			"2024-01-01",
+			"2024-01-02",
+			"2024-01-03",
+			"2024-01-05",
+			"2024-01-06",
+		]
+		.iter()
+		.map(|d| date(d))
+		.collect();
+		assert_eq!(super::streaks(&days, date("2024-01-06")), (2, 3));
+		assert_eq!(super::streaks(&days, date("2024-01-07")), (2, 3));
+		assert_eq!(super::streaks(&days, date("2024-01-08")), (0, 3));
+		assert_eq!(super::streaks(&BTreeSet::new(), date("2024-01-08")), (0, 0));
+	}
+
+	#[test]
+	fn stats() {
+		let runs = vec![
+			run("2024-01-01", 8, "rs", 40),
+			run("2024-01-01", 20, "py", 60),
+			run("2024-01-02", 9, "rs", 50),
+			run("2024-01-09", 9, "rs", 70),
+		];
+		let stats = Stats::new(&runs, &Utc, Period::Day, date("2024-01-09"));
+		assert_eq!(stats.runs, 4);
+		assert_eq!(stats.total_time, 120);
+		assert_eq!(stats.current_streak, 1);
+		assert_eq!(stats.longest_streak, 2);
+		assert_eq!(stats.periods.len(), 3);
+		assert_eq!(stats.periods[0].wpm, 50.0);
+		assert_eq!(
+			stats.by_extension,
+			vec![(".py".to_string(), 60), (".rs".to_string(), 53)]
+		);
+		assert_eq!(
+			stats.by_time_of_day,
+			vec![("morning".to_string(), 53), ("evening".to_string(), 60)]
+		);
+
+		let weekly = Stats::new(&runs, &Utc, Period::Week, date("2024-01-09"));
+		assert_eq!(weekly.periods.len(), 2);
+		assert_eq!(weekly.periods[0].start, date("2024-01-01"));
+		assert_eq!(weekly.periods[0].runs, 3);
+	}
+
+	#[test]
+	fn offset() {
+		assert_eq!(
+			Period::Day.offset(date("2024-01-01"), date("2024-01-04")),
+			3.0
+		);
+		assert_eq!(
+			Period::Week.offset(date("2024-01-01"), date("2024-01-15")),
+			2.0
+		);
+	}
+}
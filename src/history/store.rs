@@ -0,0 +1,139 @@
+use crate::history::recording::Recording;
+use crate::history::run::Run;
+use anyhow::{anyhow, Result};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+const APP_DIR: &str = "terminal-typer";
+const HISTORY_FILE: &str = "history.jsonl";
+const RECORDINGS_DIR: &str = "recordings";
+
+pub struct Store {
+	dir: PathBuf,
+}
+
+impl Store {
+	pub fn new(dir: PathBuf) -> Self {
+		Store { dir }
+	}
+
+	pub fn open() -> Result<Self> {
+		dirs::data_dir()
+			.map(|d| Store::new(d.join(APP_DIR)))
+			.ok_or_else(|| anyhow!("Data directory not found."))
+	}
+
+	pub fn load(&self) -> Result<Vec<Run>> {
+		let path = self.history_path();
+		if !path.exists() {
+			return Ok(Vec::new());
+		}
+
+		Ok(fs::read_to_string(path)?
+			.lines()
+			.filter(|l| !l.trim().is_empty())
+			.filter_map(|l| serde_json::from_str(l).ok())
+			.collect())
+	}
+
+	pub fn append(&self, mut run: Run) -> Result<Run> {
+		run.id = self.load()?.iter().map(|r| r.id).max().unwrap_or(0) + 1;
+
+		fs::create_dir_all(&self.dir)?;
+		let mut file = OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(self.history_path())?;
+		writeln!(file, "{}", serde_json::to_string(&run)?)?;
+		Ok(run)
+	}
+
+	pub fn save_recording(&self, id: u64, recording: &Recording) -> Result<()> {
+		let dir = self.dir.join(RECORDINGS_DIR);
+		fs::create_dir_all(&dir)?;
+		fs::write(self.recording_path(id), serde_json::to_string(recording)?)?;
+		Ok(())
+	}
+
+	pub fn load_recording(&self, id: u64) -> Result<Recording> {
+		let path = self.recording_path(id);
+		if !path.exists() {
+			return Err(anyhow!("No recording found for run {}.", id));
+		}
+		Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+	}
+
+	fn recording_path(&self, id: u64) -> PathBuf {
+		self.dir.join(RECORDINGS_DIR).join(format!("{}.json", id))
+	}
+
+	fn history_path(&self) -> PathBuf {
+		self.dir.join(HISTORY_FILE)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::keystroke::Keystroke;
+	use crate::types::policy::ErrorPolicy;
+	use std::env;
+
+	fn temp_store(name: &str) -> Store {
+		let dir = env::temp_dir().join(format!("terminal-typer-{}-{}", name, Run::now()));
+		let _ = fs::remove_dir_all(&dir);
+		Store::new(dir)
+	}
+
+	fn run(wpm: usize) -> Run {
+		Run {
+			timestamp: Run::now(),
+			wpm,
+			typed: 10,
+			..Run::sample("src/main.rs")
+		}
+	}
+
+	#[test]
+	fn load_empty() {
+		let store = temp_store("load-empty");
+		assert!(store.load().unwrap().is_empty());
+	}
+
+	#[test]
+	fn append() {
+		let store = temp_store("append");
+		assert_eq!(store.append(run(40)).unwrap().id, 1);
+		assert_eq!(store.append(run(50)).unwrap().id, 2);
+
+		let runs = store.load().unwrap();
+		assert_eq!(runs.len(), 2);
+		assert_eq!(runs[1].wpm, 50);
+		let _ = fs::remove_dir_all(&store.dir);
+	}
+
+	#[test]
+	fn recording() {
+		let store = temp_store("recording");
+		assert!(store.load_recording(1).is_err());
+
+		let recording = Recording {
+			text: "fn main() {}".to_string(),
+			policy: ErrorPolicy::StopOnWord,
+			editor: true,
+			warm_up: false,
+			keystrokes: vec![Keystroke {
+				at: 120,
+				key: 'f',
+				expected: Some('f'),
+				correct: true,
+				line_no: 1,
+				column: 0,
+			}],
+		};
+		store.save_recording(1, &recording).unwrap();
+		assert_eq!(store.load_recording(1).unwrap(), recording);
+		let _ = fs::remove_dir_all(&store.dir);
+	}
+}
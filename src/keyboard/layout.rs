@@ -0,0 +1,215 @@
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::Path;
+
+const LAYOUTS: [(&str, &str); 4] = [
+	("qwerty", include_str!("../../res/layouts/qwerty.txt")),
+	("dvorak", include_str!("../../res/layouts/dvorak.txt")),
+	("colemak", include_str!("../../res/layouts/colemak.txt")),
+	("workman", include_str!("../../res/layouts/workman.txt")),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Finger {
+	LeftPinky,
+	LeftRing,
+	LeftMiddle,
+	LeftIndex,
+	RightIndex,
+	RightMiddle,
+	RightRing,
+	RightPinky,
+	Thumb,
+}
+
+impl Finger {
+	fn parse(c: char) -> Result<Self> {
+		match c {
+			'1' => Ok(Finger::LeftPinky),
+			'2' => Ok(Finger::LeftRing),
+			'3' => Ok(Finger::LeftMiddle),
+			'4' => Ok(Finger::LeftIndex),
+			'5' => Ok(Finger::RightIndex),
+			'6' => Ok(Finger::RightMiddle),
+			'7' => Ok(Finger::RightRing),
+			'8' => Ok(Finger::RightPinky),
+			_ => Err(anyhow!("Unknown finger {}, expected 1 to 8.", c)),
+		}
+	}
+
+	pub fn label(&self) -> &'static str {
+		match self {
+			Finger::LeftPinky => "L pinky",
+			Finger::LeftRing => "L ring",
+			Finger::LeftMiddle => "L middle",
+			Finger::LeftIndex => "L index",
+			Finger::RightIndex => "R index",
+			Finger::RightMiddle => "R middle",
+			Finger::RightRing => "R ring",
+			Finger::RightPinky => "R pinky",
+			Finger::Thumb => "thumbs",
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Key {
+	pub normal: char,
+	pub shifted: char,
+	pub finger: Finger,
+}
+
+/// Keys by row and column. Every layout shares the ANSI geometry, so the same
+/// position is the same physical key.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layout {
+	pub name: String,
+	pub rows: Vec<Vec<Key>>,
+}
+
+impl Layout {
+	/// Parses rows of three lines: unshifted keys, shifted keys and fingers 1-8.
+	pub fn parse(name: &str, text: &str) -> Result<Self> {
+		let lines: Vec<Vec<char>> = text
+			.lines()
+			.filter(|l| !l.is_empty() && !l.starts_with('#'))
+			.map(|l| l.chars().collect())
+			.collect();
+		if lines.is_empty() || lines.len() % 3 != 0 {
+			return Err(anyhow!(
+				"Layout {} needs three lines per row: keys, shifted keys and fingers.",
+				name
+			));
+		}
+
+		let rows = lines
+			.chunks(3)
+			.enumerate()
+			.map(|(i, row)| {
+				if row[0].len() != row[1].len() || row[0].len() != row[2].len() {
+					return Err(anyhow!(
+						"Row {} of layout {} has lines of different lengths.",
+						i + 1,
+						name
+					));
+				}
+				(0..row[0].len())
+					.map(|col| {
+						Ok(Key {
+							normal: row[0][col],
+							shifted: row[1][col],
+							finger: Finger::parse(row[2][col])?,
+						})
+					})
+					.collect()
+			})
+			.collect::<Result<Vec<Vec<Key>>>>()?;
+		Ok(Layout {
+			name: name.to_string(),
+			rows,
+		})
+	}
+
+	/// A bundled layout by name or a layout file by path.
+	pub fn load(name: &str) -> Result<Self> {
+		match LAYOUTS.iter().find(|(n, _)| *n == name) {
+			Some((n, text)) => Layout::parse(n, text),
+			None if Path::new(name).is_file() => Layout::parse(name, &fs::read_to_string(name)?),
+			None => Err(anyhow!(
+				"Unknown layout {}, expected one of {} or a layout file.",
+				name,
+				LAYOUTS
+					.iter()
+					.map(|(n, _)| *n)
+					.collect::<Vec<&str>>()
+					.join(", ")
+			)),
+		}
+	}
+
+	pub fn qwerty() -> Self {
+		Layout::parse(LAYOUTS[0].0, LAYOUTS[0].1).unwrap()
+	}
+
+	/// Row, column and whether shift is needed to type `c`.
+	pub fn position(&self, c: char) -> Option<(usize, usize, bool)> {
+		self.rows.iter().enumerate().find_map(|(r, keys)| {
+			keys.iter().enumerate().find_map(|(col, k)| {
+				if k.normal == c {
+					Some((r, col, false))
+				} else if k.shifted == c {
+					Some((r, col, true))
+				} else {
+					None
+				}
+			})
+		})
+	}
+
+	pub fn key(&self, row: usize, col: usize) -> Option<&Key> {
+		self.rows.get(row).and_then(|keys| keys.get(col))
+	}
+
+	pub fn finger(&self, c: char) -> Option<Finger> {
+		if c == ' ' {
+			return Some(Finger::Thumb);
+		}
+		self.position(c)
+			.and_then(|(r, col, _)| self.key(r, col))
+			.map(|k| k.finger)
+	}
+
+	/// The char this layout produces on the physical key that gives `c` on `from`.
+	pub fn remap(&self, from: &Layout, c: char) -> char {
+		from.position(c)
+			.and_then(|(r, col, shifted)| {
+				self.key(r, col)
+					.map(|k| if shifted { k.shifted } else { k.normal })
+			})
+			.unwrap_or(c)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn bundled() {
+		for (name, _) in LAYOUTS {
+			let layout = Layout::load(name).unwrap();
+			assert_eq!(
+				layout.rows.iter().map(|r| r.len()).collect::<Vec<usize>>(),
+				vec![13, 13, 11, 10]
+			);
+		}
+		assert!(Layout::load("azerty").is_err());
+	}
+
+	#[test]
+	fn parse_errors() {
+		assert!(Layout::parse("bad", "abc\nABC\n").is_err());
+		assert!(Layout::parse("bad", "abc\nAB\n123\n").is_err());
+		assert!(Layout::parse("bad", "abc\nABC\n129\n").is_err());
+	}
+
+	#[test]
+	fn remap() {
+		let qwerty = Layout::qwerty();
+		let dvorak = Layout::load("dvorak").unwrap();
+		assert_eq!(dvorak.remap(&qwerty, 'j'), 'h');
+		assert_eq!(dvorak.remap(&qwerty, 'S'), 'O');
+		assert_eq!(dvorak.remap(&qwerty, 'q'), '\'');
+		assert_eq!(dvorak.remap(&qwerty, ' '), ' ');
+		assert_eq!(qwerty.remap(&qwerty, 'x'), 'x');
+	}
+
+	#[test]
+	fn finger() {
+		let colemak = Layout::load("colemak").unwrap();
+		assert_eq!(colemak.finger('t'), Some(Finger::LeftIndex));
+		assert_eq!(colemak.finger('O'), Some(Finger::RightPinky));
+		assert_eq!(colemak.finger(' '), Some(Finger::Thumb));
+		assert_eq!(colemak.finger('é'), None);
+	}
+}
@@ -0,0 +1,95 @@
+use crate::keyboard::layout::{Finger, Layout};
+use crate::types::keystroke::Keystroke;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct KeyStat {
+	pub hits: usize,
+	pub misses: usize,
+}
+
+impl KeyStat {
+	pub fn error_rate(&self) -> f64 {
+		if self.hits == 0 {
+			0.0
+		} else {
+			self.misses as f64 / self.hits as f64
+		}
+	}
+}
+
+/// Keystrokes counted per physical key and per finger, by the char that was expected.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeyStats {
+	pub keys: BTreeMap<(usize, usize), KeyStat>,
+	pub fingers: BTreeMap<Finger, KeyStat>,
+	pub total: usize,
+}
+
+impl KeyStats {
+	pub fn new(keystrokes: &[Keystroke], layout: &Layout) -> Self {
+		let mut stats = KeyStats::default();
+		for k in keystrokes {
+			let expected = match k.expected {
+				Some(c) => c,
+				None => continue,
+			};
+			let miss = if k.correct { 0 } else { 1 };
+			if let Some((row, col, _)) = layout.position(expected) {
+				let key = stats.keys.entry((row, col)).or_default();
+				key.hits += 1;
+				key.misses += miss;
+			}
+			if let Some(finger) = layout.finger(expected) {
+				let finger = stats.fingers.entry(finger).or_default();
+				finger.hits += 1;
+				finger.misses += miss;
+				stats.total += 1;
+			}
+		}
+		stats
+	}
+
+	pub fn key(&self, row: usize, col: usize) -> KeyStat {
+		self.keys.get(&(row, col)).cloned().unwrap_or_default()
+	}
+
+	/// Share of all keystrokes typed with `finger`.
+	pub fn load(&self, finger: Finger) -> f64 {
+		let hits = self.fingers.get(&finger).map(|f| f.hits).unwrap_or(0);
+		hits as f64 / self.total.max(1) as f64
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn key(expected: char, correct: bool) -> Keystroke {
+		Keystroke {
+			at: 0,
+			key: expected,
+			expected: Some(expected),
+			correct,
+			line_no: 1,
+			column: 0,
+		}
+	}
+
+	#[test]
+	fn stats() {
+		let keystrokes = vec![
+			key('a', true),
+			key('a', false),
+			key('A', true),
+			key(' ', true),
+			key('j', true),
+		];
+		let stats = KeyStats::new(&keystrokes, &Layout::qwerty());
+		assert_eq!(stats.key(2, 0), KeyStat { hits: 3, misses: 1 });
+		assert_eq!(stats.total, 5);
+		assert_eq!(stats.load(Finger::LeftPinky), 0.6);
+		assert_eq!(stats.load(Finger::Thumb), 0.2);
+		assert_eq!(stats.fingers[&Finger::LeftPinky].error_rate(), 1.0 / 3.0);
+	}
+}
@@ -0,0 +1,2 @@
+//! The typing model, shared by the app and its benchmarks.
+pub mod types;
@@ -1,31 +1,80 @@
 use anyhow::{anyhow, Result};
-use clap::Parser;
+use chrono::{Local, TimeZone};
+use clap::{Parser, Subcommand};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    cursor::Show,
+    event::{
+        self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture,
+        Event, KeyCode, KeyModifiers, MouseButton, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ignore::Walk;
-use rand::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+use std::cmp;
+use std::fs;
 use std::io;
-use std::path::PathBuf;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
-use tui::{backend::CrosstermBackend, Terminal};
+use terminal_typer::types;
+use tui::{
+    backend::{Backend, CrosstermBackend},
+    Terminal,
+};
 
 mod app;
+mod config;
+mod events;
+mod history;
+mod keyboard;
+mod picker;
 mod reader;
-mod types;
 mod views;
-use crate::views::{view, Theme};
+use crate::views::{click_at, navigation_view, picker_view, stats_view, view, Click, Theme};
 use app::App;
-use reader::file::FileReader;
+use config::{Config, Goals};
+use events::{AppEvent, Events};
+use history::bests::Bests;
+use history::goals::DailyProgress;
+use history::recording::Recording;
+use history::run::Run;
+use history::stats::{Period, Stats};
+use history::store::Store;
+use keyboard::layout::Layout;
+use picker::Picker;
+use reader::file::{FileReader, DEFAULT_MAX_BYTES, DEFAULT_WINDOW_LINES};
+use reader::finder::{list_files, parse_size, pick_file, FileFilter, WeightBy, Weights};
+use reader::quotes::QuotesReader;
 use reader::reader::Reader;
+use reader::scope::{ScopeFilter, ScopeReader};
+use reader::symbols::{Density, SymbolsReader};
+use reader::words::{WordList, WordsReader};
+use types::navigation::Navigation;
+use types::policy::ErrorPolicy;
+use types::start::StartMode;
 use types::typing::Typing;
 
 const QUIT_COMMAND: char = 'q';
 const EXIT_COMMAND: char = 'c';
 const RESTART_COMMAND: char = 'r';
+const NEXT_COMMAND: char = 'n';
+const PERIOD_COMMAND: char = 'w';
+const TIME_COMMAND: char = 't';
+const KEYBOARD_COMMAND: char = 'k';
 const ONE_SEC: Duration = Duration::from_secs(1);
+const REPLAY_FRAME: Duration = Duration::from_millis(50);
+/// Beyond any display's refresh rate, so more frames would only burn CPU.
+const MAX_FPS: u32 = 240;
+/// How often clocks and animations advance: the countdown, a mistype flash and the tenths timer.
+const TICK_RATE: Duration = Duration::from_millis(100);
+const BESTS_LIMIT: usize = 5;
+const MIN_CORPUS_TIME: usize = 120;
+const WORDS_PER_SECOND: usize = 4;
+const AVERAGE_WORD_LENGTH: usize = 5;
 
 #[derive(Parser, Debug)]
 #[clap(author, about, long_about = None, version = "v0.1.0")]
@@ -42,181 +91,961 @@ struct Args {
     #[clap(short = 'd', parse(from_os_str), value_name = "dir", value_hint = clap::ValueHint::DirPath)]
     dir: Option<PathBuf>,
 
-    #[clap(short = 'e', long)]
-    extension: Option<String>,
+    #[clap(
+        short = 'e',
+        long,
+        multiple_occurrences = true,
+        use_value_delimiter = true
+    )]
+    extension: Vec<String>,
+
+    #[clap(long, value_name = "glob", multiple_occurrences = true)]
+    include: Vec<String>,
+
+    #[clap(long, value_name = "glob", multiple_occurrences = true)]
+    exclude: Vec<String>,
+
+    #[clap(long, value_name = "bytes", parse(try_from_str = parse_size))]
+    min_size: Option<u64>,
+
+    #[clap(long, value_name = "bytes", parse(try_from_str = parse_size))]
+    max_size: Option<u64>,
+
+    #[clap(long, value_name = "lines")]
+    min_lines: Option<usize>,
+
+    #[clap(long, value_name = "lines")]
+    max_lines: Option<usize>,
+
+    #[clap(long, value_name = "length|language", parse(try_from_str = WeightBy::parse))]
+    weight: Option<WeightBy>,
+
+    #[clap(long)]
+    seed: Option<u64>,
+
+    /// Choose the file interactively, returning to the picker after each run
+    #[clap(short = 'p', long, conflicts_with_all = &["file", "seed"])]
+    pick: bool,
+
+    /// How often the results chart samples speed and accuracy, in milliseconds
+    #[clap(long, value_name = "ms", default_value_t = 1000, validator = |v: &str| match v.parse::<u64>() {
+        Ok(ms) if ms > 0 => Ok(()),
+        _ => Err("must be a positive number of milliseconds"),
+    })]
+    sample_interval: u64,
+
+    /// Practice this layout (qwerty, dvorak, colemak, workman or a layout file) on the OS layout
+    #[clap(long, value_name = "layout", parse(try_from_str = Layout::load))]
+    layout: Option<Layout>,
+
+    /// The layout the operating system is set to
+    #[clap(long, value_name = "layout", default_value = "qwerty", parse(try_from_str = Layout::load))]
+    os_layout: Layout,
+
+    /// Show a keyboard under the text with the next key highlighted
+    #[clap(long)]
+    keyboard: bool,
+
+    /// Skip closing brackets and quotes as an editor would insert them; tab toggles it per run
+    #[clap(long)]
+    editor: bool,
+
+    /// Most frames drawn per second; frames are only drawn when something changed
+    #[clap(long, value_name = "fps", default_value_t = 30, validator = |v: &str| match v.parse::<u32>() {
+        Ok(fps) if (1..=MAX_FPS).contains(&fps) => Ok(()),
+        _ => Err(format!("must be a number of frames from 1 to {}", MAX_FPS)),
+    })]
+    fps: u32,
+
+    /// Show the remaining time in tenths of a second
+    #[clap(long)]
+    tenths: bool,
+
+    /// Leave the mouse to the terminal, so text can be selected
+    #[clap(long)]
+    no_mouse: bool,
+
+    /// Read at most this many bytes of a file, stopping at the last whole line
+    #[clap(long, value_name = "bytes", default_value_t = DEFAULT_MAX_BYTES)]
+    max_bytes: u64,
+
+    /// Read at most this many lines of a file
+    #[clap(long, value_name = "lines", default_value_t = DEFAULT_WINDOW_LINES, validator = |v: &str| match v.parse::<usize>() {
+        Ok(lines) if lines > 0 => Ok(()),
+        _ => Err("must be a number of lines above 0"),
+    })]
+    window: usize,
+
+    /// Practice vim motions on the text instead of typing it
+    #[clap(long, conflicts_with = "pick")]
+    vim: bool,
+
+    /// What starts the timer: key, enter or countdown
+    #[clap(long, value_name = "mode", parse(try_from_str = StartMode::parse))]
+    start: Option<StartMode>,
+
+    /// Type the first line as an unscored warm-up before the timer starts
+    #[clap(long)]
+    warm_up: bool,
+
+    /// What a wrong key does: letter, advance, word or sudden-death
+    #[clap(long, value_name = "policy", parse(try_from_str = ErrorPolicy::parse))]
+    on_error: Option<ErrorPolicy>,
 
     #[clap(short = 't', default_value = "dark")]
     theme: String,
+
+    #[clap(long, value_name = "200|1k|10k", conflicts_with_all = &["keywords", "quotes"])]
+    words: Option<String>,
+
+    #[clap(long, value_name = "extension", conflicts_with = "quotes")]
+    keywords: Option<String>,
+
+    #[clap(long)]
+    quotes: bool,
+
+    #[clap(long)]
+    punctuation: bool,
+
+    #[clap(long)]
+    numbers: bool,
+
+    #[clap(long, conflicts_with_all = &["words", "keywords", "quotes"])]
+    symbols: bool,
+
+    #[clap(long, conflicts_with_all = &["collapse-docs", "comments-only"])]
+    strip_comments: bool,
+
+    #[clap(long, conflicts_with = "comments-only")]
+    collapse_docs: bool,
+
+    #[clap(long)]
+    comments_only: bool,
+
+    #[clap(long, value_name = "percent", default_value_t = 30)]
+    symbol_density: usize,
+
+    #[clap(long, value_name = "percent", default_value_t = 20)]
+    literal_density: usize,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the personal best leaderboard per language
+    Bests {
+        #[clap(long, default_value_t = BESTS_LIMIT)]
+        limit: usize,
+    },
+    /// Show long-term progress charts from the run history
+    Stats,
+    /// Play back a recorded run keystroke by keystroke
+    Replay {
+        #[clap(value_name = "run-id")]
+        id: u64,
+
+        /// Playback speed: 1x, 2x or 4x (press 1, 2 or 4 while playing to change it)
+        #[clap(long, default_value = "1x", parse(try_from_str = parse_speed))]
+        speed: u32,
+    },
+}
+
+/// Settings shared by every test started in this session.
+#[derive(Clone)]
+struct Options {
+    time: Duration,
+    display_line: usize,
+    policy: ErrorPolicy,
+    sample_interval: Duration,
+    goals: Goals,
+    durations: Vec<Duration>,
+    start_mode: StartMode,
+    warm_up: bool,
+    os_layout: Layout,
+    layout: Layout,
+    keyboard: bool,
+    editor: bool,
+    vim: bool,
+    mouse: bool,
+    fps: u32,
+    tenths: bool,
+    max_bytes: u64,
+    window: usize,
 }
 
-fn close_app() -> Result<()> {
+impl Options {
+    fn file_reader(&self, file: PathBuf) -> FileReader {
+        FileReader::new(file)
+            .with_max_bytes(self.max_bytes)
+            .with_window(self.window)
+    }
+
+    fn app(&self, text: &str) -> Result<App> {
+        Ok(App::new(text, self.time, self.display_line)?
+            .with_policy(self.policy)
+            .with_sample_interval(self.sample_interval)
+            .with_goals(self.goals)
+            .with_durations(self.durations.clone())
+            .with_start_mode(self.start_mode)
+            .with_warm_up(self.warm_up)
+            .with_layouts(self.os_layout.clone(), self.layout.clone())
+            .with_keyboard_panel(self.keyboard)
+            .with_editor(self.editor)
+            .with_tenths(self.tenths)
+            .with_frame_rate(Duration::from_secs(1) / self.fps))
+    }
+}
+
+enum Exit {
+    Quit,
+    Next,
+}
+
+/// Whether a `Screen` has the terminal, so the panic hook knows to restore it.
+static SCREEN_OPEN: AtomicBool = AtomicBool::new(false);
+
+/// The terminal in raw mode on the alternate screen. Dropping it puts the terminal
+/// back, so an early return doesn't leave the shell unusable.
+struct Screen {
+    terminal: Terminal<CrosstermBackend<io::Stdout>>,
+    mouse: bool,
+}
+
+impl Screen {
+    fn open(mouse: bool) -> io::Result<Self> {
+        enable_raw_mode()?;
+        SCREEN_OPEN.store(true, Ordering::SeqCst);
+        // From here the guard owns the terminal, so a failure below still restores it.
+        let guard = ScreenGuard(mouse);
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen, EnableFocusChange)?;
+        if mouse {
+            execute!(stdout, EnableMouseCapture)?;
+        }
+        let terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+        mem::forget(guard);
+        Ok(Screen { terminal, mouse })
+    }
+}
+
+/// Restores the terminal if `Screen::open` fails part way.
+struct ScreenGuard(bool);
+
+impl Drop for ScreenGuard {
+    fn drop(&mut self) {
+        let _ = restore_terminal(self.0);
+    }
+}
+
+impl Deref for Screen {
+    type Target = Terminal<CrosstermBackend<io::Stdout>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.terminal
+    }
+}
+
+impl DerefMut for Screen {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.terminal
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = restore_terminal(self.mouse);
+    }
+}
+
+fn restore_terminal(mouse: bool) -> io::Result<()> {
+    if !SCREEN_OPEN.swap(false, Ordering::SeqCst) {
+        return Ok(());
+    }
     disable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, LeaveAlternateScreen, DisableMouseCapture)?;
-    Ok(())
+    if mouse {
+        execute!(stdout, DisableMouseCapture)?;
+    }
+    execute!(stdout, DisableFocusChange, LeaveAlternateScreen, Show)
 }
 
-fn run_app(mut app: App, text: &str, theme: Theme, file: PathBuf) -> io::Result<()> {
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
-    let mut last_tick = Instant::now();
+/// Restores the terminal before the panic message is printed, so it isn't lost on
+/// the alternate screen.
+fn restore_on_panic() {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal(true);
+        hook(info);
+    }));
+}
+
+fn record_run(app: App, store: &Option<Store>, file: &Path, text: &str) -> App {
+    match store {
+        Some(store) if app.typing.typed() > 0 => {
+            let run = app.run(&fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf()));
+            let bests = store
+                .load()
+                .map(|runs| Bests::new(runs).compare(&run))
+                .unwrap_or_default();
+            if let Ok(run) = store.append(run) {
+                let _ = store.save_recording(run.id, &app.recording(text));
+            }
+            refresh_daily(app.with_bests(bests), Some(store))
+        }
+        _ => app,
+    }
+}
+
+fn refresh_daily(app: App, store: Option<&Store>) -> App {
+    let runs = store.and_then(|s| s.load().ok());
+    match runs {
+        Some(runs) => {
+            let daily = DailyProgress::new(&runs, app.goals(), &Local, Local::now().date_naive());
+            app.with_daily(daily)
+        }
+        None => app,
+    }
+}
+
+fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    mut app: App,
+    text: &str,
+    theme: &Theme,
+    file: PathBuf,
+    store: &Option<Store>,
+    picking: bool,
+) -> io::Result<Exit> {
+    // Whole seconds taken off the clock, derived from the running time so they never drift.
+    let mut ticks = 0;
+    app = refresh_daily(app, store.as_ref());
+    let events = Events::new(TICK_RATE, app.frame_rate());
+    let mut hotspots = Vec::new();
+    let mut dirty = true;
+    let mut flashing = false;
 
     loop {
-        terminal.draw(|f| view(f, &app, &theme, file.clone()))?;
+        let was_finish = app.typing.is_finish();
 
-        let timeout = ONE_SEC
-            .checked_sub(last_tick.elapsed())
-            .unwrap_or_else(|| Duration::from_secs(0));
+        match events.next()? {
+            AppEvent::Render => {
+                if dirty {
+                    terminal.draw(|f| hotspots = view(f, &app, theme, file.clone(), picking))?;
+                    flashing = app.flash().is_some();
+                    dirty = false;
+                }
+            }
+            AppEvent::Tick => {
+                if app.countdown().is_some() {
+                    app = app.update();
+                    dirty = true;
+                }
 
-        if crossterm::event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                match app.typing {
-                    Typing::BeforeStart(_) => match key.code {
-                        KeyCode::Right => {
-                            app = app.next_time();
-                        }
-                        KeyCode::Left => {
-                            app = app.prev_time();
-                        }
-                        KeyCode::Char(QUIT_COMMAND) => {
-                            return Ok(());
-                        }
-                        KeyCode::Char(EXIT_COMMAND) if key.modifiers == KeyModifiers::CONTROL => {
-                            return Ok(());
+                if !matches!(app.typing, Typing::Running(_)) || app.typing.is_warming_up() {
+                    ticks = 0;
+                } else if !app.is_paused() {
+                    while matches!(app.typing, Typing::Running(_))
+                        && ONE_SEC * (ticks + 1) <= app.typing.running_time()
+                    {
+                        app = app.tick();
+                        ticks += 1;
+                        dirty = true;
+                    }
+                }
+
+                let running = matches!(app.typing, Typing::Running(_)) && !app.is_paused();
+                if flashing != app.flash().is_some() || (app.tenths() && running) {
+                    dirty = true;
+                }
+            }
+            AppEvent::Input(event) => {
+                dirty = true;
+                match event {
+                    Event::Mouse(mouse) => match mouse.kind {
+                        MouseEventKind::Down(MouseButton::Left) => {
+                            match click_at(&hotspots, mouse.column, mouse.row) {
+                                Some(Click::Time(time)) => app = app.select_time(time),
+                                Some(Click::Restart) => app = app.restart(text),
+                                Some(Click::Quit) => return Ok(Exit::Quit),
+                                Some(Click::Next) => return Ok(Exit::Next),
+                                None => (),
+                            }
                         }
-                        KeyCode::Char(c) => {
-                            app = app.start().input(c);
+                        MouseEventKind::Moved if app.typing.is_finish() => {
+                            app = app.with_hover(Some((mouse.column, mouse.row)));
                         }
                         _ => (),
                     },
-                    Typing::Running(_) => match key.code {
-                        KeyCode::Enter => {
-                            app = app.input('\n');
-                        }
-                        KeyCode::Char(EXIT_COMMAND) if key.modifiers == KeyModifiers::CONTROL => {
+                    Event::Resize(_, _) => {
+                        terminal.autoresize()?;
+                        app = app.with_hover(None);
+                    }
+                    Event::FocusLost => app = app.pause(),
+                    Event::Key(key) if app.is_paused() => {
+                        app = app.resume();
+                        if key.code == KeyCode::Char(EXIT_COMMAND)
+                            && key.modifiers == KeyModifiers::CONTROL
+                        {
                             app = app.finish();
                         }
-                        KeyCode::Char(c) => {
-                            app = app.input(c);
-                        }
-                        _ => (),
+                    }
+                    Event::Key(key) => match app.typing {
+                        Typing::BeforeStart(_) if app.time_input().is_some() => match key.code {
+                            KeyCode::Enter => app = app.submit_time(),
+                            KeyCode::Esc => app = app.cancel_time_input(),
+                            KeyCode::Backspace => app = app.delete_time(),
+                            KeyCode::Char(EXIT_COMMAND)
+                                if key.modifiers == KeyModifiers::CONTROL =>
+                            {
+                                return Ok(Exit::Quit);
+                            }
+                            KeyCode::Char(c) => app = app.input_time(c),
+                            _ => (),
+                        },
+                        Typing::BeforeStart(_) if app.countdown().is_some() => match key.code {
+                            KeyCode::Char(EXIT_COMMAND)
+                                if key.modifiers == KeyModifiers::CONTROL =>
+                            {
+                                return Ok(Exit::Quit);
+                            }
+                            _ => (),
+                        },
+                        Typing::BeforeStart(_) => match key.code {
+                            KeyCode::Enter if app.start_mode() == StartMode::Enter => {
+                                app = app.start();
+                            }
+                            KeyCode::Enter if app.start_mode() == StartMode::Countdown => {
+                                app = app.begin_countdown();
+                            }
+                            KeyCode::Right => {
+                                app = app.next_time();
+                            }
+                            KeyCode::Left => {
+                                app = app.prev_time();
+                            }
+                            KeyCode::Tab => {
+                                app = app.toggle_editor();
+                            }
+                            KeyCode::Char(QUIT_COMMAND) => {
+                                return Ok(Exit::Quit);
+                            }
+                            KeyCode::Char(EXIT_COMMAND)
+                                if key.modifiers == KeyModifiers::CONTROL =>
+                            {
+                                return Ok(Exit::Quit);
+                            }
+                            KeyCode::Esc if picking => {
+                                return Ok(Exit::Next);
+                            }
+                            KeyCode::Char(TIME_COMMAND)
+                                if key.modifiers == KeyModifiers::CONTROL =>
+                            {
+                                app = app.start_time_input();
+                            }
+                            KeyCode::Char(c) if app.start_mode() == StartMode::FirstKey => {
+                                app = app.start().input(c);
+                            }
+                            _ => (),
+                        },
+                        Typing::Running(_) => match key.code {
+                            KeyCode::Enter => {
+                                app = app.input('\n');
+                            }
+                            KeyCode::Char(EXIT_COMMAND)
+                                if key.modifiers == KeyModifiers::CONTROL =>
+                            {
+                                app = app.finish();
+                            }
+                            KeyCode::Char(c) => {
+                                app = app.input(c);
+                            }
+                            _ => (),
+                        },
+                        Typing::Finish(_) => match key.code {
+                            KeyCode::Char(RESTART_COMMAND) => app = app.restart(text),
+                            KeyCode::Char(KEYBOARD_COMMAND) => app = app.toggle_keyboard(),
+                            KeyCode::Char(NEXT_COMMAND) if picking => {
+                                return Ok(Exit::Next);
+                            }
+                            KeyCode::Char(QUIT_COMMAND) => {
+                                return Ok(Exit::Quit);
+                            }
+                            KeyCode::Char(EXIT_COMMAND)
+                                if key.modifiers == KeyModifiers::CONTROL =>
+                            {
+                                return Ok(Exit::Quit);
+                            }
+                            _ => (),
+                        },
                     },
-                    Typing::Finish(_) => match key.code {
-                        KeyCode::Char(RESTART_COMMAND) => app = app.restart(text),
-                        KeyCode::Char(QUIT_COMMAND) => {
-                            return Ok(());
+                    _ => (),
+                }
+            }
+        }
+
+        if !was_finish && app.typing.is_finish() {
+            app = record_run(app, store, &file, text);
+        }
+    }
+}
+
+fn run_replay<B: Backend>(
+    terminal: &mut Terminal<B>,
+    mut app: App,
+    recording: &Recording,
+    run: &Run,
+    mut speed: u32,
+    theme: &Theme,
+) -> io::Result<()> {
+    let mut keystrokes = recording.keystrokes.iter().peekable();
+    let end = Duration::from_secs(run.elapsed);
+    let mut position = Duration::from_secs(0);
+    let mut ticks = 0;
+    let mut last_frame = Instant::now();
+
+    loop {
+        position += last_frame.elapsed() * speed;
+        last_frame = Instant::now();
+
+        if !app.typing.is_finish() {
+            app = app.with_elapsed(position);
+            while let Some(k) = keystrokes.next_if(|k| Duration::from_millis(k.at) <= position) {
+                app = app.input(k.key);
+            }
+            while !app.typing.is_finish() && ONE_SEC * (ticks + 1) <= position {
+                app = app.tick();
+                ticks += 1;
+            }
+            if keystrokes.peek().is_none() && position >= end {
+                app = app.finish();
+            }
+        }
+
+        terminal.draw(|f| {
+            view(f, &app, theme, run.file.clone(), false);
+        })?;
+
+        if crossterm::event::poll(REPLAY_FRAME)? {
+            if let Event::Key(key) = event::read()? {
+                match key.code {
+                    KeyCode::Char(QUIT_COMMAND) | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char(EXIT_COMMAND) if key.modifiers == KeyModifiers::CONTROL => {
+                        return Ok(());
+                    }
+                    KeyCode::Char(KEYBOARD_COMMAND) => app = app.toggle_keyboard(),
+                    KeyCode::Char(c) => {
+                        if let Ok(s) = parse_speed(&c.to_string()) {
+                            speed = s;
                         }
-                        KeyCode::Char(EXIT_COMMAND) if key.modifiers == KeyModifiers::CONTROL => {
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+fn replay(id: u64, speed: u32, options: &Options, theme: Theme) -> Result<()> {
+    let store = Store::open()?;
+    let run = store
+        .load()?
+        .into_iter()
+        .find(|r| r.id == id)
+        .ok_or_else(|| anyhow!("No run with id {}.", id))?;
+    let recording = store.load_recording(id)?;
+    let options = Options {
+        time: Duration::from_secs(run.duration),
+        policy: recording.policy,
+        editor: recording.editor,
+        warm_up: recording.warm_up,
+        // Recorded keys are already in the practiced layout.
+        os_layout: options.layout.clone(),
+        ..options.clone()
+    };
+    let app = options.app(&recording.text)?.start().skip_warm_up();
+
+    let mut screen = Screen::open(options.mouse)?;
+    run_replay(&mut screen, app, &recording, &run, speed, &theme)?;
+    Ok(())
+}
+
+fn parse_speed(speed: &str) -> Result<u32> {
+    match speed.trim_end_matches('x') {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        "4" => Ok(4),
+        _ => Err(anyhow!("Replay speed must be 1x, 2x or 4x.")),
+    }
+}
+
+fn run_picker<B: Backend>(
+    terminal: &mut Terminal<B>,
+    mut picker: Picker,
+    theme: &Theme,
+    rng: &mut StdRng,
+) -> io::Result<(Picker, Option<PathBuf>)> {
+    loop {
+        terminal.draw(|f| picker_view(f, &picker, theme))?;
+
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Esc => return Ok((picker, None)),
+                KeyCode::Char(EXIT_COMMAND) if key.modifiers == KeyModifiers::CONTROL => {
+                    return Ok((picker, None));
+                }
+                KeyCode::Enter => {
+                    if let Some(file) = picker.selected() {
+                        return Ok((picker, Some(file)));
+                    }
+                }
+                KeyCode::Tab => {
+                    if let Some(file) = picker.random(rng) {
+                        return Ok((picker, Some(file)));
+                    }
+                }
+                KeyCode::Down => picker = picker.next(),
+                KeyCode::Up => picker = picker.prev(),
+                KeyCode::Backspace => picker = picker.backspace(),
+                KeyCode::Char(c) => picker = picker.input(c),
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Loads a file and whether only its start was read.
+fn load_file(
+    file: &Path,
+    scope_filter: Option<ScopeFilter>,
+    options: &Options,
+) -> Result<(String, bool)> {
+    let reader = options.file_reader(file.to_path_buf());
+    let text = match scope_filter {
+        Some(filter) => ScopeReader::new(&reader, filter).load(),
+        None => reader.load(),
+    }?;
+    Ok((text, reader.truncated()))
+}
+
+fn start_typing(reader: &dyn Reader, options: &Options, theme: Theme) -> Result<()> {
+    if options.vim {
+        return start_navigation(reader, options, theme);
+    }
+    let file = reader.source();
+    let text = reader.load()?;
+    let app = options.app(&text)?.with_truncated(reader.truncated());
+    let mut screen = Screen::open(options.mouse)?;
+    run_app(
+        &mut screen,
+        app,
+        &text,
+        &theme,
+        file,
+        &Store::open().ok(),
+        false,
+    )?;
+    Ok(())
+}
+
+fn run_navigation<B: Backend>(
+    terminal: &mut Terminal<B>,
+    mut navigation: Navigation,
+    time: Duration,
+    theme: &Theme,
+    file: PathBuf,
+    truncated: bool,
+) -> io::Result<()> {
+    let mut last_tick = Instant::now();
+
+    loop {
+        terminal.draw(|f| navigation_view(f, &navigation, theme, file.clone(), truncated))?;
+
+        let timeout = ONE_SEC
+            .checked_sub(last_tick.elapsed())
+            .unwrap_or_else(|| Duration::from_secs(0));
+
+        if crossterm::event::poll(timeout)? {
+            if let Event::Key(key) = event::read()? {
+                match key.code {
+                    KeyCode::Char(EXIT_COMMAND) if key.modifiers == KeyModifiers::CONTROL => {
+                        if let Navigation::Running(_) = navigation {
+                            navigation = navigation.finish();
+                        } else {
                             return Ok(());
                         }
-                        _ => (),
-                    },
+                    }
+                    KeyCode::Char(QUIT_COMMAND)
+                        if !matches!(navigation, Navigation::Running(_)) =>
+                    {
+                        return Ok(());
+                    }
+                    KeyCode::Char(RESTART_COMMAND) if navigation.is_finish() => {
+                        navigation = navigation.restart(time);
+                    }
+                    KeyCode::Esc => navigation = navigation.clear(),
+                    KeyCode::Char(c) if navigation.is_before_start() => {
+                        navigation = navigation.start().input(c);
+                        last_tick = Instant::now();
+                    }
+                    KeyCode::Char(c) => navigation = navigation.input(c),
+                    _ => (),
                 }
             }
         }
 
         if last_tick.elapsed() >= ONE_SEC {
-            match app.typing {
-                Typing::Running(_) => {
-                    app = app.tick();
-                    last_tick = Instant::now();
+            navigation = navigation.tick();
+            last_tick = Instant::now();
+        }
+    }
+}
+
+fn start_navigation(reader: &dyn Reader, options: &Options, theme: Theme) -> Result<()> {
+    let file = reader.source();
+    let text = App::filter_text(&reader.load()?);
+    let navigation = Navigation::new(&text, options.time, options.display_line)?;
+
+    let mut screen = Screen::open(options.mouse)?;
+    run_navigation(
+        &mut screen,
+        navigation,
+        options.time,
+        &theme,
+        file,
+        reader.truncated(),
+    )?;
+    Ok(())
+}
+
+fn start_picker(
+    files: Vec<PathBuf>,
+    weight_by: Option<WeightBy>,
+    scope_filter: Option<ScopeFilter>,
+    options: &Options,
+    theme: Theme,
+) -> Result<()> {
+    let store = Store::open().ok();
+    let mut rng = StdRng::from_entropy();
+    let mut picker = Picker::new(files);
+    let mut screen = Screen::open(options.mouse)?;
+
+    loop {
+        let runs = store
+            .as_ref()
+            .and_then(|s| s.load().ok())
+            .unwrap_or_default();
+        picker = picker.with_history(&runs, Weights::new(&runs, weight_by, Run::now()));
+
+        let (next, file) = run_picker(&mut screen, picker, &theme, &mut rng)?;
+        picker = next;
+        let file = match file {
+            Some(file) => file,
+            None => return Ok(()),
+        };
+
+        let app = load_file(&file, scope_filter, options).and_then(|(text, truncated)| {
+            options
+                .app(&text)
+                .map(|app| (app.with_truncated(truncated), text))
+        });
+        match app {
+            Ok((app, text)) => {
+                match run_app(&mut screen, app, &text, &theme, file, &store, true)? {
+                    Exit::Quit => return Ok(()),
+                    Exit::Next => (),
                 }
-                _ => (),
+            }
+            Err(err) => {
+                picker = picker.with_message(format!("{}: {}", file.display(), err));
             }
         }
     }
 }
 
-fn start_typing(file: PathBuf, time: Duration, display_line: usize, theme: Theme) -> Result<()> {
-    let reader = FileReader::new(file.clone());
-    match reader.load() {
-        Ok(text) => {
-            let app = App::new(&text, time, display_line)?;
-            let res = run_app(app, &text, theme, file);
+fn run_stats<B: Backend>(
+    terminal: &mut Terminal<B>,
+    runs: &[Run],
+    theme: &Theme,
+) -> io::Result<()> {
+    let mut period = Period::Day;
+    let mut stats = Stats::new(runs, &Local, period, Local::now().date_naive());
 
-            if let Err(err) = res {
-                return Err(anyhow!(format!("{:?}", err)));
-            }
+    loop {
+        terminal.draw(|f| stats_view(f, &stats, period, theme))?;
 
-            close_app()?;
-            Ok(())
-        }
-        Err(_) => Err(anyhow!(format!("Failed to load file."))),
-    }
-}
-
-fn list_files(path: PathBuf, target_extension: Option<String>) -> Vec<PathBuf> {
-    Walk::new(path)
-        .filter_map(|e| match e {
-            Ok(entry) => {
-                let extension = entry
-                    .path()
-                    .extension()
-                    .and_then(|f| f.to_str())
-                    .unwrap_or("")
-                    .to_lowercase();
-                let match_extension = |target: Option<String>, e: String| {
-                    target.map(|t| e == t.to_lowercase()).unwrap_or(true)
-                };
-
-                if entry.file_type().unwrap().is_file()
-                    && extension != ""
-                    && match_extension(target_extension.clone(), extension.to_string().clone())
-                {
-                    Some(entry)
-                } else {
-                    None
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Char(QUIT_COMMAND) | KeyCode::Esc => return Ok(()),
+                KeyCode::Char(EXIT_COMMAND) if key.modifiers == KeyModifiers::CONTROL => {
+                    return Ok(());
+                }
+                KeyCode::Char(PERIOD_COMMAND) => {
+                    period = period.toggle();
+                    stats = Stats::new(runs, &Local, period, Local::now().date_naive());
                 }
+                _ => (),
             }
-            _ => None,
-        })
-        .map(|e| e.into_path())
-        .collect()
+        }
+    }
 }
 
-fn pick_file(path: PathBuf, target_extension: Option<String>) -> Option<PathBuf> {
-    let files = list_files(path, target_extension);
+fn show_stats(theme: Theme, mouse: bool) -> Result<()> {
+    let runs = Store::open()?.load()?;
+    if runs.is_empty() {
+        println!("No runs recorded yet.");
+        return Ok(());
+    }
 
-    if files.len() == 0 {
-        return None;
+    let mut screen = Screen::open(mouse)?;
+    run_stats(&mut screen, &runs, &theme)?;
+    Ok(())
+}
+
+fn print_bests(limit: usize) -> Result<()> {
+    let runs = Store::open()?.load()?;
+    if runs.is_empty() {
+        println!("No runs recorded yet.");
+        return Ok(());
     }
 
-    let mut rng = rand::thread_rng();
-    let file_index = rng.gen_range(0..files.len());
-    let file = &files[file_index];
-    Some(file.clone())
+    for ((extension, policy), runs) in Bests::new(runs).leaderboard(limit) {
+        if policy == ErrorPolicy::default() {
+            println!(".{}", extension);
+        } else {
+            println!(".{} ({})", extension, policy.label());
+        }
+        for (i, run) in runs.iter().enumerate() {
+            let date = Local
+                .timestamp_opt(run.timestamp as i64, 0)
+                .single()
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+            println!(
+                "  {:>2}. {:>4} wpm {:>4}% {:>5}s  {}  {}",
+                i + 1,
+                run.wpm,
+                run.acc,
+                run.duration,
+                date,
+                run.file.display()
+            );
+        }
+    }
+    Ok(())
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    restore_on_panic();
+
+    let theme = Theme::new(&args.theme);
+    let config = Config::load()?;
+    let options = Options {
+        time: Duration::from_secs(args.time as u64),
+        display_line: args.line,
+        policy: args.on_error.unwrap_or_default(),
+        sample_interval: Duration::from_millis(args.sample_interval),
+        goals: config.goals,
+        durations: config.durations()?,
+        start_mode: args.start.unwrap_or_default(),
+        warm_up: args.warm_up,
+        layout: args.layout.unwrap_or_else(|| args.os_layout.clone()),
+        os_layout: args.os_layout,
+        keyboard: args.keyboard,
+        editor: args.editor,
+        vim: args.vim,
+        mouse: !args.no_mouse,
+        fps: args.fps,
+        tenths: args.tenths,
+        max_bytes: args.max_bytes,
+        window: args.window,
+    };
+
+    match args.command {
+        Some(Command::Bests { limit }) => return print_bests(limit),
+        Some(Command::Stats) => return show_stats(theme, options.mouse),
+        Some(Command::Replay { id, speed }) => return replay(id, speed, &options, theme),
+        None => (),
+    }
+
+    let word_count = cmp::max(args.time, MIN_CORPUS_TIME) * WORDS_PER_SECOND;
+
+    if args.quotes {
+        let reader = QuotesReader::new(word_count * AVERAGE_WORD_LENGTH);
+        return start_typing(&reader, &options, theme);
+    }
+
+    if args.symbols {
+        let density = Density::new(args.symbol_density, args.literal_density)?;
+        let reader = SymbolsReader::new(word_count, density);
+        return start_typing(&reader, &options, theme);
+    }
+
+    let word_list = match (args.words, args.keywords) {
+        (Some(words), _) => Some(WordList::parse(&words)?),
+        (_, Some(keywords)) => Some(WordList::keywords(&keywords)?),
+        _ => None,
+    };
+    if let Some(list) = word_list {
+        let reader = WordsReader::new(list, word_count, args.punctuation, args.numbers);
+        return start_typing(&reader, &options, theme);
+    }
+
+    let scope_filter = if args.strip_comments {
+        Some(ScopeFilter::StripComments)
+    } else if args.collapse_docs {
+        Some(ScopeFilter::CollapseDocs)
+    } else if args.comments_only {
+        Some(ScopeFilter::CommentsOnly)
+    } else {
+        None
+    };
+    let filter = FileFilter {
+        extensions: args.extension,
+        include: args.include,
+        exclude: args.exclude,
+        min_size: args.min_size,
+        max_size: args.max_size,
+        min_lines: args.min_lines,
+        max_lines: args.max_lines,
+    };
+    let dir = args.dir.unwrap_or_else(|| PathBuf::from(r"."));
+
+    if args.pick {
+        let files = list_files(&dir, &filter)?;
+        if files.is_empty() {
+            return Err(anyhow!("No files found in {}.", dir.display()));
+        }
+        return start_picker(files, args.weight, scope_filter, &options, theme);
+    }
+
+    let file = match args.file {
+        Some(file) => file,
+        None => {
+            // A seeded pick ignores history, so it only depends on the seed, the directory
+            // and the weighting.
+            let (weights, mut rng) = match args.seed {
+                Some(seed) => (
+                    Weights::new(&[], args.weight, 0),
+                    StdRng::seed_from_u64(seed),
+                ),
+                None => (
+                    Store::open()
+                        .and_then(|s| s.load())
+                        .map(|runs| Weights::new(&runs, args.weight, Run::now()))
+                        .unwrap_or_default(),
+                    StdRng::from_entropy(),
+                ),
+            };
+            pick_file(&dir, &filter, &weights, &mut rng)?
+        }
+    };
+    let reader = options.file_reader(file);
 
-    match (args.file, args.dir) {
-        (Some(file), _) => start_typing(
-            file.clone(),
-            Duration::from_secs(args.time as u64),
-            args.line,
-            Theme::new(&args.theme),
-        ),
-        (_, Some(dir)) => match pick_file(PathBuf::from(dir), args.extension) {
-            Some(file) => start_typing(
-                file.clone(),
-                Duration::from_secs(args.time as u64),
-                args.line,
-                Theme::new(&args.theme),
-            ),
-            None => Err(anyhow!(format!("File not found."))),
-        },
-        _ => match pick_file(PathBuf::from(r"."), args.extension) {
-            Some(file) => start_typing(
-                file.clone(),
-                Duration::from_secs(args.time as u64),
-                args.line,
-                Theme::new(&args.theme),
-            ),
-            None => Err(anyhow!(format!("File not found."))),
-        },
+    match scope_filter {
+        Some(filter) => start_typing(&ScopeReader::new(&reader, filter), &options, theme),
+        None => start_typing(&reader, &options, theme),
     }
 }
@@ -0,0 +1,289 @@
+use crate::history::run::Run;
+use crate::reader::finder::Weights;
+use rand::prelude::*;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const PREVIEW_BYTES: u64 = 8192;
+const SEPARATORS: &str = "/\\_-. ";
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FileStats {
+	pub practiced: usize,
+	pub last_wpm: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Picker {
+	files: Vec<PathBuf>,
+	stats: HashMap<PathBuf, FileStats>,
+	weights: Weights,
+	query: String,
+	/// Files matching the query, best first, and the start of the selected one,
+	/// kept so drawing a frame doesn't filter or read files.
+	matches: Vec<PathBuf>,
+	preview: String,
+	selected: usize,
+	message: Option<String>,
+}
+
+impl Picker {
+	pub fn new(files: Vec<PathBuf>) -> Self {
+		Picker {
+			files,
+			stats: HashMap::new(),
+			weights: Weights::uniform(),
+			query: String::new(),
+			matches: Vec::new(),
+			preview: String::new(),
+			selected: 0,
+			message: None,
+		}
+		.filter()
+	}
+
+	pub fn with_history(mut self, runs: &[Run], weights: Weights) -> Self {
+		let mut by_file: HashMap<PathBuf, FileStats> = HashMap::new();
+		let mut runs: Vec<&Run> = runs.iter().collect();
+		runs.sort_by_key(|r| r.timestamp);
+		for run in runs {
+			let stats = by_file.entry(run.file.clone()).or_default();
+			stats.practiced += 1;
+			stats.last_wpm = Some(run.wpm);
+		}
+
+		self.stats = self
+			.files
+			.iter()
+			.filter_map(|f| {
+				let canonical = fs::canonicalize(f).unwrap_or_else(|_| f.clone());
+				by_file.get(&canonical).map(|s| (f.clone(), s.clone()))
+			})
+			.collect();
+		self.weights = weights;
+		self
+	}
+
+	pub fn with_message(mut self, message: String) -> Self {
+		self.message = Some(message);
+		self
+	}
+
+	pub fn input(mut self, c: char) -> Self {
+		self.query.push(c);
+		self.message = None;
+		self.filter()
+	}
+
+	pub fn backspace(mut self) -> Self {
+		self.query.pop();
+		self.message = None;
+		self.filter()
+	}
+
+	pub fn next(mut self) -> Self {
+		let len = self.matches.len();
+		if len > 0 {
+			self.selected = (self.selected + 1) % len;
+		}
+		self.load_preview()
+	}
+
+	pub fn prev(mut self) -> Self {
+		let len = self.matches.len();
+		if len > 0 {
+			self.selected = (self.selected + len - 1) % len;
+		}
+		self.load_preview()
+	}
+
+	/// Matches the files against the query and selects the best one.
+	fn filter(mut self) -> Self {
+		let mut scored: Vec<(i64, &PathBuf)> = self
+			.files
+			.iter()
+			.filter_map(|f| fuzzy_score(&self.query, &f.to_string_lossy()).map(|s| (s, f)))
+			.collect();
+		scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+		self.matches = scored.into_iter().map(|(_, f)| f.clone()).collect();
+		self.selected = 0;
+		self.load_preview()
+	}
+
+	/// Reads the start of the selected file. A char split by the cut, or any
+	/// invalid UTF-8, is replaced rather than blanking the preview.
+	fn load_preview(mut self) -> Self {
+		let mut bytes = Vec::new();
+		if let Some(f) = self.selected().and_then(|f| File::open(f).ok()) {
+			let _ = f.take(PREVIEW_BYTES).read_to_end(&mut bytes);
+		}
+		self.preview = String::from_utf8_lossy(&bytes)
+			.trim_end_matches(char::REPLACEMENT_CHARACTER)
+			.replace('\t', "    ");
+		self
+	}
+
+	pub fn query(&self) -> &str {
+		&self.query
+	}
+
+	pub fn message(&self) -> Option<&String> {
+		self.message.as_ref()
+	}
+
+	pub fn selected_index(&self) -> usize {
+		self.selected
+	}
+
+	pub fn total(&self) -> usize {
+		self.files.len()
+	}
+
+	pub fn matches(&self) -> &[PathBuf] {
+		&self.matches
+	}
+
+	pub fn selected(&self) -> Option<PathBuf> {
+		self.matches.get(self.selected).cloned()
+	}
+
+	pub fn random<R: Rng>(&self, rng: &mut R) -> Option<PathBuf> {
+		self.matches
+			.choose_weighted(rng, |f| self.weights.weight(f))
+			.ok()
+			.map(|f| f.to_path_buf())
+	}
+
+	pub fn stats(&self, file: &Path) -> FileStats {
+		self.stats.get(file).cloned().unwrap_or_default()
+	}
+
+	pub fn preview(&self) -> &str {
+		&self.preview
+	}
+}
+
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+	let query: Vec<char> = query.to_lowercase().chars().collect();
+	let mut score = 0;
+	let mut matched = 0;
+	let mut prev: Option<char> = None;
+	let mut prev_matched = false;
+
+	for c in text.to_lowercase().chars() {
+		if matched < query.len() && c == query[matched] {
+			score += 1;
+			if prev_matched {
+				score += 5;
+			}
+			if prev.map(|p| SEPARATORS.contains(p)).unwrap_or(true) {
+				score += 3;
+			}
+			matched += 1;
+			prev_matched = true;
+		} else {
+			prev_matched = false;
+		}
+		prev = Some(c);
+	}
+
+	if matched == query.len() {
+		Some(score)
+	} else {
+		None
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::rngs::StdRng;
+
+	fn picker() -> Picker {
+		Picker::new(vec![
+			PathBuf::from("src/app.rs"),
+			PathBuf::from("src/main.rs"),
+			PathBuf::from("src/types/typing.rs"),
+		])
+	}
+
+	#[test]
+	fn fuzzy() {
+		assert!(fuzzy_score("mn", "src/main.rs").is_some());
+		assert!(fuzzy_score("nm", "src/main.rs").is_none());
+		assert!(fuzzy_score("main", "src/main.rs") > fuzzy_score("main", "src/m_a_i_n.rs"));
+		assert_eq!(fuzzy_score("", "anything"), Some(0));
+	}
+
+	#[test]
+	fn filter() {
+		let picker = picker().input('t').input('y');
+		assert_eq!(picker.matches(), vec![PathBuf::from("src/types/typing.rs")]);
+		assert_eq!(picker.backspace().backspace().matches().len(), 3);
+	}
+
+	#[test]
+	fn select() {
+		let picker = picker();
+		assert_eq!(picker.selected(), Some(PathBuf::from("src/app.rs")));
+		assert_eq!(
+			picker.clone().next().selected(),
+			Some(PathBuf::from("src/main.rs"))
+		);
+		assert_eq!(
+			picker.prev().selected(),
+			Some(PathBuf::from("src/types/typing.rs"))
+		);
+	}
+
+	#[test]
+	fn random() {
+		let picker = picker().input('m').input('a').input('i');
+		assert_eq!(
+			picker.random(&mut StdRng::seed_from_u64(0)),
+			Some(PathBuf::from("src/main.rs"))
+		);
+		assert_eq!(
+			picker.input('z').random(&mut StdRng::seed_from_u64(0)),
+			None
+		);
+	}
+
+	#[test]
+	fn stats() {
+		let run = |wpm, timestamp| Run {
+			timestamp,
+			file: fs::canonicalize("src/main.rs").unwrap(),
+			wpm,
+			..Run::sample("main.rs")
+		};
+		let picker = picker().with_history(&[run(60, 2), run(40, 1)], Weights::uniform());
+		assert_eq!(
+			picker.stats(Path::new("src/main.rs")),
+			FileStats {
+				practiced: 2,
+				last_wpm: Some(60)
+			}
+		);
+		assert_eq!(picker.stats(Path::new("src/app.rs")), FileStats::default());
+	}
+
+	#[test]
+	fn preview() {
+		let dir =
+			std::env::temp_dir().join(format!("terminal-typer-picker-{}", std::process::id()));
+		fs::create_dir_all(&dir).unwrap();
+		let file = dir.join("cut.txt");
+		let mut text = "a".repeat(PREVIEW_BYTES as usize - 1).into_bytes();
+		text.extend("\u{e9}\tb".bytes());
+		fs::write(&file, text).unwrap();
+
+		let picker = Picker::new(vec![file]);
+		let preview = picker.preview().to_string();
+		fs::remove_dir_all(&dir).unwrap();
+		assert_eq!(preview.len(), PREVIEW_BYTES as usize - 1);
+		assert!(preview.chars().all(|c| c == 'a'));
+	}
+}
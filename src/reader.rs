@@ -1,2 +1,7 @@
 pub mod file;
+pub mod finder;
+pub mod quotes;
 pub mod reader;
+pub mod scope;
+pub mod symbols;
+pub mod words;
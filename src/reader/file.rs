@@ -1,21 +1,172 @@
 use crate::reader::reader::Reader;
-use anyhow::Result;
-use std::fs;
+use anyhow::{anyhow, Error, Result};
+use std::cell::Cell;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, ErrorKind, Read};
 use std::path::PathBuf;
 
+/// More lines than any test gets through, so a huge file is never read to the end.
+pub const DEFAULT_WINDOW_LINES: usize = 5_000;
+pub const DEFAULT_MAX_BYTES: u64 = 1024 * 1024;
+
 pub struct FileReader {
 	path: PathBuf,
+	max_bytes: u64,
+	window: usize,
+	truncated: Cell<bool>,
 }
 
 impl FileReader {
 	pub fn new(path: PathBuf) -> Self {
-		FileReader { path: path }
+		FileReader {
+			path: path,
+			max_bytes: DEFAULT_MAX_BYTES,
+			window: DEFAULT_WINDOW_LINES,
+			truncated: Cell::new(false),
+		}
+	}
+
+	/// Stops reading at the last whole line within `max_bytes`.
+	pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+		self.max_bytes = max_bytes;
+		self
+	}
+
+	/// Stops reading after `window` lines.
+	pub fn with_window(mut self, window: usize) -> Self {
+		self.window = window;
+		self
+	}
+
+	fn error(&self, err: io::Error) -> Error {
+		let path = self.path.display();
+		match err.kind() {
+			ErrorKind::NotFound => anyhow!("{} not found.", path),
+			ErrorKind::PermissionDenied => anyhow!("Permission denied reading {}.", path),
+			ErrorKind::InvalidData => anyhow!("{} is not valid UTF-8.", path),
+			_ => anyhow!("Failed to read {}: {}.", path, err),
+		}
 	}
 }
 
 impl Reader for FileReader {
+	/// Reads the file a line at a time, up to `window` lines or `max_bytes`,
+	/// never reading more than one byte past the cap.
 	fn load(&self) -> Result<String> {
-		let text = fs::read_to_string(self.path.clone())?;
+		let file = File::open(&self.path).map_err(|err| self.error(err))?;
+		let mut reader = BufReader::new(file);
+		let mut text = Vec::new();
+		let mut line = Vec::new();
+		let mut truncated = false;
+		for _ in 0..self.window {
+			line.clear();
+			let left = self.max_bytes - text.len() as u64;
+			let read = (&mut reader)
+				.take(left.saturating_add(1))
+				.read_until(b'\n', &mut line)
+				.map_err(|err| self.error(err))?;
+			if read == 0 {
+				break;
+			}
+			if read as u64 > left {
+				if text.is_empty() {
+					return Err(anyhow!(
+						"{} starts with a line over the {} byte limit.",
+						self.path.display(),
+						self.max_bytes
+					));
+				}
+				truncated = true;
+				break;
+			}
+			text.extend_from_slice(&line);
+		}
+		if !truncated {
+			truncated = !reader.fill_buf().map_err(|err| self.error(err))?.is_empty();
+		}
+		self.truncated.set(truncated);
+
+		let text = String::from_utf8(text)
+			.map_err(|_| self.error(io::Error::from(ErrorKind::InvalidData)))?;
+		if text.trim().is_empty() {
+			return Err(anyhow!("{} is empty.", self.path.display()));
+		}
 		Ok(text)
 	}
+
+	fn truncated(&self) -> bool {
+		self.truncated.get()
+	}
+
+	fn source(&self) -> PathBuf {
+		self.path.clone()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::{env, fs, process};
+
+	/// A file unique to the test and process, removed when dropped.
+	struct TempFile(PathBuf);
+
+	impl Drop for TempFile {
+		fn drop(&mut self) {
+			let _ = fs::remove_file(&self.0);
+		}
+	}
+
+	fn temp_file(name: &str, contents: &[u8]) -> TempFile {
+		let path = env::temp_dir().join(format!("terminal-typer-file-{}-{}", name, process::id()));
+		fs::write(&path, contents).unwrap();
+		TempFile(path)
+	}
+
+	#[test]
+	fn load_errors() {
+		let missing = FileReader::new(PathBuf::from("/nonexistent/file.rs"));
+		assert!(missing
+			.load()
+			.unwrap_err()
+			.to_string()
+			.contains("not found"));
+
+		let binary = temp_file("binary", &[0x66, 0xff, 0xfe]);
+		let reader = FileReader::new(binary.0.clone());
+		assert!(reader.load().unwrap_err().to_string().contains("UTF-8"));
+
+		let empty = temp_file("empty", b"  \n");
+		let reader = FileReader::new(empty.0.clone());
+		assert!(reader.load().unwrap_err().to_string().contains("empty"));
+
+		let text = temp_file("text", b"fn main() {}\n");
+		let reader = FileReader::new(text.0.clone());
+		assert_eq!(reader.load().unwrap(), "fn main() {}\n");
+	}
+
+	#[test]
+	fn window() {
+		let file = temp_file("window", b"abc\ndef\nghi\n");
+		let reader = FileReader::new(file.0.clone()).with_max_bytes(9);
+		assert_eq!(reader.load().unwrap(), "abc\ndef\n");
+		assert!(reader.truncated());
+
+		let reader = FileReader::new(file.0.clone()).with_max_bytes(12);
+		assert_eq!(reader.load().unwrap(), "abc\ndef\nghi\n");
+		assert!(!reader.truncated());
+
+		let reader = FileReader::new(file.0.clone()).with_window(1);
+		assert_eq!(reader.load().unwrap(), "abc\n");
+		assert!(reader.truncated());
+
+		let reader = FileReader::new(file.0.clone()).with_max_bytes(2);
+		assert!(reader.load().unwrap_err().to_string().contains("limit"));
+
+		let long = "x\n".repeat(DEFAULT_WINDOW_LINES + 10);
+		let file = temp_file("long", long.as_bytes());
+		let reader = FileReader::new(file.0.clone());
+		assert_eq!(reader.load().unwrap().lines().count(), DEFAULT_WINDOW_LINES);
+		assert!(reader.truncated());
+	}
 }
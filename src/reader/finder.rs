@@ -0,0 +1,509 @@
+use crate::history::run::Run;
+use anyhow::{anyhow, Result};
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
+use rand::prelude::*;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::str;
+
+const DEFAULT_EXCLUDES: [&str; 8] = [
+	"*.lock",
+	"*-lock.json",
+	"*-lock.yaml",
+	"*.min.js",
+	"*.min.css",
+	"*.map",
+	"*.svg",
+	"*.snap",
+];
+const BINARY_SNIFF_LEN: usize = 8000;
+/// How much of a candidate is read to tell whether it's text worth typing.
+const CHECK_PREFIX_LEN: u64 = 64 * 1024;
+const MINIFIED_LINE_LEN: usize = 500;
+const MAX_AGE_DAYS: f64 = 30.0;
+const SECS_PER_DAY: f64 = 86400.0;
+
+#[derive(Clone, Debug, Default)]
+pub struct FileFilter {
+	pub extensions: Vec<String>,
+	pub include: Vec<String>,
+	pub exclude: Vec<String>,
+	pub min_size: Option<u64>,
+	pub max_size: Option<u64>,
+	pub min_lines: Option<usize>,
+	pub max_lines: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WeightBy {
+	Length,
+	Language,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Weights {
+	last_practiced: HashMap<PathBuf, u64>,
+	language_runs: HashMap<String, usize>,
+	by: Option<WeightBy>,
+	now: u64,
+}
+
+impl WeightBy {
+	pub fn parse(name: &str) -> Result<Self> {
+		match name {
+			"length" => Ok(WeightBy::Length),
+			"language" => Ok(WeightBy::Language),
+			_ => Err(anyhow!(
+				"Unknown weighting {}, expected length or language.",
+				name
+			)),
+		}
+	}
+}
+
+impl Weights {
+	pub fn uniform() -> Self {
+		Weights::default()
+	}
+
+	pub fn new(runs: &[Run], by: Option<WeightBy>, now: u64) -> Self {
+		let mut last_practiced: HashMap<PathBuf, u64> = HashMap::new();
+		let mut language_runs: HashMap<String, usize> = HashMap::new();
+		for run in runs.iter() {
+			let last = last_practiced.entry(run.file.clone()).or_insert(0);
+			*last = (*last).max(run.timestamp);
+			*language_runs.entry(run.extension.clone()).or_insert(0) += 1;
+		}
+		Weights {
+			last_practiced,
+			language_runs,
+			by,
+			now,
+		}
+	}
+
+	pub fn weight(&self, path: &Path) -> f64 {
+		let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+		let recency = match self.last_practiced.get(&canonical) {
+			Some(last) => {
+				1.0 + (self.now.saturating_sub(*last) as f64 / SECS_PER_DAY).min(MAX_AGE_DAYS)
+			}
+			None if self.now > 0 => 2.0 * (1.0 + MAX_AGE_DAYS),
+			None => 1.0,
+		};
+		let factor = match self.by {
+			Some(WeightBy::Length) => fs::metadata(path)
+				.map(|m| (m.len() as f64).sqrt().max(1.0))
+				.unwrap_or(1.0),
+			Some(WeightBy::Language) => {
+				let runs = self
+					.language_runs
+					.get(&Run::extension_of(path))
+					.copied()
+					.unwrap_or(0);
+				1.0 / (1.0 + runs as f64)
+			}
+			None => 1.0,
+		};
+		recency * factor
+	}
+}
+
+impl FileFilter {
+	pub fn match_extension(&self, path: &Path) -> bool {
+		let extension = path
+			.extension()
+			.and_then(|f| f.to_str())
+			.unwrap_or("")
+			.to_lowercase();
+		!extension.is_empty()
+			&& (self.extensions.is_empty()
+				|| self
+					.extensions
+					.iter()
+					.any(|e| e.trim_start_matches('.').to_lowercase() == extension))
+	}
+
+	pub fn match_size(&self, size: u64) -> bool {
+		self.min_size.map(|m| size >= m).unwrap_or(true)
+			&& self.max_size.map(|m| size <= m).unwrap_or(true)
+	}
+
+	/// Rejects files that aren't worth typing, reading no more than a prefix of each
+	/// unless a line bound needs every line counted.
+	pub fn check(&self, path: &Path) -> Result<()> {
+		let size = fs::metadata(path)?.len();
+		if !self.match_size(size) {
+			return Err(anyhow!(
+				"{} has {} bytes, outside the allowed range.",
+				path.display(),
+				size
+			));
+		}
+
+		let mut bytes = Vec::new();
+		File::open(path)?
+			.take(CHECK_PREFIX_LEN)
+			.read_to_end(&mut bytes)?;
+		if bytes[..bytes.len().min(BINARY_SNIFF_LEN)].contains(&0) {
+			return Err(anyhow!("{} looks like a binary file.", path.display()));
+		}
+
+		// The prefix may end partway through a char, which doesn't make the file invalid.
+		let text = match str::from_utf8(&bytes) {
+			Ok(text) => text,
+			Err(err) if err.error_len().is_none() => {
+				str::from_utf8(&bytes[..err.valid_up_to()]).unwrap_or_default()
+			}
+			Err(_) => return Err(anyhow!("{} is not valid UTF-8.", path.display())),
+		};
+		if text.trim().is_empty() {
+			return Err(anyhow!("{} is empty.", path.display()));
+		}
+		if text.lines().any(|l| l.len() > MINIFIED_LINE_LEN) {
+			return Err(anyhow!("{} looks minified.", path.display()));
+		}
+
+		if self.min_lines.is_none() && self.max_lines.is_none() {
+			return Ok(());
+		}
+		let lines = count_lines(path)?;
+		if self.min_lines.map(|m| lines < m).unwrap_or(false)
+			|| self.max_lines.map(|m| lines > m).unwrap_or(false)
+		{
+			return Err(anyhow!(
+				"{} has {} lines, outside the allowed range.",
+				path.display(),
+				lines
+			));
+		}
+		Ok(())
+	}
+}
+
+/// Counts lines the way `str::lines` does, without holding the file in memory.
+fn count_lines(path: &Path) -> Result<usize> {
+	let mut reader = BufReader::new(File::open(path)?);
+	let mut lines = 0;
+	let mut last = b'\n';
+	loop {
+		let buf = reader.fill_buf()?;
+		let len = buf.len();
+		if len == 0 {
+			break;
+		}
+		lines += buf.iter().filter(|b| **b == b'\n').count();
+		last = buf[len - 1];
+		reader.consume(len);
+	}
+	Ok(if last == b'\n' { lines } else { lines + 1 })
+}
+
+pub fn list_files(path: &Path, filter: &FileFilter) -> Result<Vec<PathBuf>> {
+	let mut overrides = OverrideBuilder::new(path);
+	for glob in filter.include.iter() {
+		overrides.add(glob)?;
+	}
+	for glob in DEFAULT_EXCLUDES
+		.iter()
+		.map(|g| g.to_string())
+		.chain(filter.exclude.clone())
+	{
+		overrides.add(&format!("!{}", glob))?;
+	}
+
+	let mut files: Vec<PathBuf> = WalkBuilder::new(path)
+		.overrides(overrides.build()?)
+		.build()
+		.filter_map(|e| e.ok())
+		.filter(|e| e.file_type().map(|t| t.is_file()).unwrap_or(false))
+		.filter(|e| filter.match_extension(e.path()))
+		.filter(|e| {
+			e.metadata()
+				.map(|m| filter.match_size(m.len()))
+				.unwrap_or(false)
+		})
+		.map(|e| e.into_path())
+		.collect();
+	files.sort();
+	Ok(files)
+}
+
+pub fn pick_file<R: Rng>(
+	path: &Path,
+	filter: &FileFilter,
+	weights: &Weights,
+	rng: &mut R,
+) -> Result<PathBuf> {
+	let files = list_files(path, filter)?;
+	if files.is_empty() {
+		return Err(anyhow!("No files found in {}.", path.display()));
+	}
+
+	// Weighted shuffle (Efraimidis-Spirakis), so bad candidates fall back to the next best pick.
+	let mut keyed: Vec<(f64, PathBuf)> = files
+		.into_iter()
+		.map(|f| {
+			let u: f64 = rng.gen_range(f64::EPSILON..1.0);
+			(u.powf(1.0 / weights.weight(&f)), f)
+		})
+		.collect();
+	keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+	let skipped = keyed.len();
+	keyed
+		.into_iter()
+		.map(|(_, f)| f)
+		.find(|f| filter.check(f).is_ok())
+		.ok_or_else(|| {
+			anyhow!(
+				"No suitable file found in {} ({} candidates skipped as binary, minified, empty or out of bounds).",
+				path.display(),
+				skipped
+			)
+		})
+}
+
+pub fn parse_size(size: &str) -> Result<u64> {
+	let size = size.trim().to_lowercase();
+	let (number, unit) = match size.find(|c: char| !c.is_ascii_digit()) {
+		Some(i) => size.split_at(i),
+		None => (size.as_str(), ""),
+	};
+	let number: u64 = number
+		.parse()
+		.map_err(|_| anyhow!("Invalid size {}, expected e.g. 512, 10k or 2m.", size))?;
+	match unit {
+		"" | "b" => Some(number),
+		"k" | "kb" => number.checked_mul(1024),
+		"m" | "mb" => number.checked_mul(1024 * 1024),
+		_ => return Err(anyhow!("Unknown size unit {}, expected b, k or m.", unit)),
+	}
+	.ok_or_else(|| anyhow!("Size {} is too large.", size))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::rngs::StdRng;
+	use std::env;
+	use std::ops::Deref;
+	use std::process;
+
+	/// A directory of test files, unique to the test and process, removed when dropped.
+	struct TempDir(PathBuf);
+
+	impl Deref for TempDir {
+		type Target = Path;
+
+		fn deref(&self) -> &Path {
+			&self.0
+		}
+	}
+
+	impl Drop for TempDir {
+		fn drop(&mut self) {
+			let _ = fs::remove_dir_all(&self.0);
+		}
+	}
+
+	fn temp_dir(name: &str, files: &[(&str, &[u8])]) -> TempDir {
+		let dir = env::temp_dir().join(format!("terminal-typer-finder-{}-{}", name, process::id()));
+		let _ = fs::remove_dir_all(&dir);
+		fs::create_dir_all(&dir).unwrap();
+		for (name, content) in files {
+			fs::write(dir.join(name), content).unwrap();
+		}
+		TempDir(dir)
+	}
+
+	#[test]
+	fn extensions() {
+		let dir = temp_dir(
+			"extensions",
+			&[
+				("a.rs", b"fn a() {}"),
+				("b.go", b"func b() {}"),
+				("c.py", b"c = 1"),
+				("Makefile", b"all:"),
+			],
+		);
+		let filter = FileFilter {
+			extensions: vec!["rs".to_string(), ".GO".to_string()],
+			..FileFilter::default()
+		};
+		assert_eq!(list_files(&dir, &filter).unwrap().len(), 2);
+		assert_eq!(list_files(&dir, &FileFilter::default()).unwrap().len(), 3);
+	}
+
+	#[test]
+	fn globs() {
+		let dir = temp_dir(
+			"globs",
+			&[
+				("a.rs", b"fn a() {}"),
+				("a_test.rs", b"fn t() {}"),
+				("Cargo.lock", b"[[package]]"),
+			],
+		);
+		let filter = FileFilter {
+			exclude: vec!["*_test.rs".to_string()],
+			..FileFilter::default()
+		};
+		assert_eq!(list_files(&dir, &filter).unwrap(), vec![dir.join("a.rs")]);
+
+		let filter = FileFilter {
+			include: vec!["*_test.rs".to_string()],
+			..FileFilter::default()
+		};
+		assert_eq!(
+			list_files(&dir, &filter).unwrap(),
+			vec![dir.join("a_test.rs")]
+		);
+	}
+
+	#[test]
+	fn size() {
+		let dir = temp_dir("size", &[("a.rs", b"fn a() {}"), ("b.rs", &[b'a'; 2048])]);
+		let filter = FileFilter {
+			max_size: Some(1024),
+			..FileFilter::default()
+		};
+		assert_eq!(list_files(&dir, &filter).unwrap(), vec![dir.join("a.rs")]);
+	}
+
+	#[test]
+	fn skip_bad_candidates() {
+		let minified = "x".repeat(MINIFIED_LINE_LEN + 1);
+		let dir = temp_dir(
+			"skip",
+			&[
+				("a.bin", b"\x00\x01\x02"),
+				("b.js", minified.as_bytes()),
+				("c.rs", b"fn c() {}"),
+			],
+		);
+		for seed in 0..5 {
+			let file = pick_file(
+				&dir,
+				&FileFilter::default(),
+				&Weights::uniform(),
+				&mut StdRng::seed_from_u64(seed),
+			);
+			assert_eq!(file.unwrap(), dir.join("c.rs"));
+		}
+	}
+
+	#[test]
+	fn no_suitable_file() {
+		let dir = temp_dir("none", &[("a.bin", b"\x00\x01\x02")]);
+		assert!(pick_file(
+			&dir,
+			&FileFilter::default(),
+			&Weights::uniform(),
+			&mut StdRng::seed_from_u64(0)
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn seeded_pick() {
+		let dir = temp_dir(
+			"seeded",
+			&[
+				("a.rs", b"a"),
+				("b.rs", b"b"),
+				("c.rs", b"c"),
+				("d.rs", b"d"),
+			],
+		);
+		let pick = |seed| {
+			pick_file(
+				&dir,
+				&FileFilter::default(),
+				&Weights::uniform(),
+				&mut StdRng::seed_from_u64(seed),
+			)
+			.unwrap()
+		};
+		assert_eq!(pick(7), pick(7));
+		assert!((0..20)
+			.map(pick)
+			.collect::<Vec<PathBuf>>()
+			.windows(2)
+			.any(|w| w[0] != w[1]));
+	}
+
+	#[test]
+	fn recency_weight() {
+		let dir = temp_dir("recency", &[("a.rs", b"a"), ("b.rs", b"b"), ("c.rs", b"c")]);
+		let now = 100 * SECS_PER_DAY as u64;
+		let run = |file: &str, timestamp| Run {
+			timestamp,
+			file: fs::canonicalize(dir.join(file)).unwrap(),
+			..Run::sample(file)
+		};
+		let weights = Weights::new(
+			&[
+				run("a.rs", now),
+				run("b.rs", now - 10 * SECS_PER_DAY as u64),
+			],
+			None,
+			now,
+		);
+
+		assert!(weights.weight(&dir.join("a.rs")) < weights.weight(&dir.join("b.rs")));
+		assert!(weights.weight(&dir.join("b.rs")) < weights.weight(&dir.join("c.rs")));
+	}
+
+	#[test]
+	fn language_weight() {
+		let dir = temp_dir("language", &[("a.rs", b"a"), ("b.go", b"b")]);
+		let run = Run::sample("other.rs");
+		let weights = Weights::new(&[run], Some(WeightBy::Language), 1);
+		assert!(weights.weight(&dir.join("a.rs")) < weights.weight(&dir.join("b.go")));
+	}
+
+	#[test]
+	fn line_bounds() {
+		let dir = temp_dir("lines", &[("a.rs", b"a\nb\nc")]);
+		let filter = FileFilter {
+			min_lines: Some(5),
+			..FileFilter::default()
+		};
+		assert!(filter.check(&dir.join("a.rs")).is_err());
+
+		let filter = FileFilter {
+			min_lines: Some(3),
+			max_lines: Some(3),
+			..FileFilter::default()
+		};
+		assert!(filter.check(&dir.join("a.rs")).is_ok());
+	}
+
+	#[test]
+	fn check_prefix() {
+		// A multi-byte char split by the prefix cut is still valid UTF-8.
+		let mut text = "a\n".repeat(CHECK_PREFIX_LEN as usize / 2 - 1).into_bytes();
+		text.extend("x\u{e9}\n".bytes());
+		let dir = temp_dir("prefix", &[("a.rs", &text)]);
+		assert!(FileFilter::default().check(&dir.join("a.rs")).is_ok());
+
+		let filter = FileFilter {
+			max_size: Some(8),
+			..FileFilter::default()
+		};
+		assert!(filter.check(&dir.join("a.rs")).is_err());
+	}
+
+	#[test]
+	fn sizes() {
+		assert_eq!(parse_size("100").unwrap(), 100);
+		assert_eq!(parse_size("2k").unwrap(), 2048);
+		assert_eq!(parse_size("1MB").unwrap(), 1024 * 1024);
+		assert!(parse_size("1g").is_err());
+		assert!(parse_size("99999999999999999m").is_err());
+	}
+}
@@ -0,0 +1,71 @@
+use crate::reader::reader::Reader;
+use crate::reader::words::{wrap, LINE_WIDTH};
+use anyhow::Result;
+use rand::prelude::*;
+use std::path::PathBuf;
+
+const QUOTES: &str = include_str!("../../res/quotes.txt");
+
+pub struct QuotesReader {
+	length: usize,
+}
+
+impl QuotesReader {
+	pub fn new(length: usize) -> Self {
+		QuotesReader { length }
+	}
+
+	pub fn quotes() -> Vec<(&'static str, &'static str)> {
+		QUOTES.lines().filter_map(|l| l.split_once('\t')).collect()
+	}
+
+	pub fn generate<R: Rng>(&self, rng: &mut R) -> String {
+		let mut quotes = QuotesReader::quotes();
+		quotes.shuffle(rng);
+
+		let mut length = 0;
+		quotes
+			.iter()
+			.take_while(|(quote, _)| {
+				let take = length < self.length;
+				length += quote.len();
+				take
+			})
+			.map(|(quote, author)| {
+				let words: Vec<&str> = quote.split_whitespace().collect();
+				format!("{}\n- {}", wrap(&words, LINE_WIDTH), author)
+			})
+			.collect::<Vec<String>>()
+			.join("\n")
+	}
+}
+
+impl Reader for QuotesReader {
+	fn load(&self) -> Result<String> {
+		Ok(self.generate(&mut rand::thread_rng()))
+	}
+
+	fn source(&self) -> PathBuf {
+		PathBuf::from("english.quotes")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::rngs::StdRng;
+
+	#[test]
+	fn quotes_have_attribution() {
+		let quotes = QuotesReader::quotes();
+		assert!(!quotes.is_empty());
+		assert!(quotes.iter().all(|(q, a)| !q.is_empty() && !a.is_empty()));
+	}
+
+	#[test]
+	fn generate() {
+		let text = QuotesReader::new(200).generate(&mut StdRng::seed_from_u64(1));
+		assert!(text.len() >= 200);
+		assert!(text.lines().any(|l| l.starts_with("- ")));
+	}
+}
@@ -1,5 +1,12 @@
 use anyhow::Result;
+use std::path::PathBuf;
 
 pub trait Reader {
 	fn load(&self) -> Result<String>;
+	fn source(&self) -> PathBuf;
+
+	/// Whether the last `load` stopped before the end of the source.
+	fn truncated(&self) -> bool {
+		false
+	}
 }
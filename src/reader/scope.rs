@@ -0,0 +1,278 @@
+use crate::reader::reader::Reader;
+use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+use syntect::parsing::{ParseState, ScopeStack, SyntaxSet};
+
+const DOC_OPENERS: [(&str, &str); 5] = [
+	("\"\"\"", "\"\"\""),
+	("'''", "'''"),
+	("/**", " */"),
+	("/*!", " */"),
+	("/*", " */"),
+];
+const DOC_MARKERS: &str = "/*!#\"'";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScopeFilter {
+	StripComments,
+	CollapseDocs,
+	CommentsOnly,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+	Code,
+	Comment,
+	Documentation,
+}
+
+type Segments = Vec<(Kind, String)>;
+
+pub struct ScopeReader<'a> {
+	inner: &'a dyn Reader,
+	filter: ScopeFilter,
+}
+
+impl<'a> ScopeReader<'a> {
+	pub fn new(inner: &'a dyn Reader, filter: ScopeFilter) -> Self {
+		ScopeReader { inner, filter }
+	}
+}
+
+impl<'a> Reader for ScopeReader<'a> {
+	fn load(&self) -> Result<String> {
+		let text = self.inner.load()?;
+		let extension = self
+			.source()
+			.extension()
+			.and_then(|e| e.to_str())
+			.unwrap_or("")
+			.to_string();
+		let filtered = match classify(&text, &extension) {
+			Some(lines) => self.filter.apply(&lines),
+			None => text,
+		};
+
+		if filtered.trim().is_empty() {
+			Err(anyhow!("Nothing left to type after filtering."))
+		} else {
+			Ok(filtered)
+		}
+	}
+
+	fn source(&self) -> PathBuf {
+		self.inner.source()
+	}
+
+	fn truncated(&self) -> bool {
+		self.inner.truncated()
+	}
+}
+
+impl ScopeFilter {
+	fn apply(&self, lines: &[Segments]) -> String {
+		match self {
+			ScopeFilter::StripComments => lines
+				.iter()
+				.filter_map(|segments| {
+					let code = text_of(segments, |k| k == Kind::Code);
+					if code.trim().is_empty() && segments.iter().any(|(k, _)| *k != Kind::Code) {
+						None
+					} else {
+						Some(code.trim_end().to_string())
+					}
+				})
+				.collect::<Vec<String>>()
+				.join("\n"),
+			ScopeFilter::CommentsOnly => lines
+				.iter()
+				.map(|segments| text_of(segments, |k| k != Kind::Code).trim().to_string())
+				.filter(|l| !l.is_empty())
+				.collect::<Vec<String>>()
+				.join("\n"),
+			ScopeFilter::CollapseDocs => {
+				let mut result: Vec<String> = Vec::new();
+				let mut block: Vec<String> = Vec::new();
+				for segments in lines.iter() {
+					if is_documentation(segments) {
+						block.push(text_of(segments, |_| true));
+					} else {
+						result.extend(collapse(&block));
+						block.clear();
+						result.push(text_of(segments, |_| true));
+					}
+				}
+				result.extend(collapse(&block));
+				result.join("\n")
+			}
+		}
+	}
+}
+
+fn classify(text: &str, extension: &str) -> Option<Vec<Segments>> {
+	let syntax_set = SyntaxSet::load_defaults_newlines();
+	let syntax = syntax_set.find_syntax_by_extension(extension)?;
+	let mut state = ParseState::new(syntax);
+	let mut stack = ScopeStack::new();
+
+	let lines = text
+		.split_inclusive('\n')
+		.map(|line| {
+			let mut segments: Segments = Vec::new();
+			let mut last = 0;
+			for (i, op) in state.parse_line(line, &syntax_set) {
+				if i > last {
+					segments.push((kind_of(&stack), line[last..i].to_string()));
+					last = i;
+				}
+				stack.apply(&op);
+			}
+			segments.push((kind_of(&stack), line[last..].to_string()));
+			segments
+				.into_iter()
+				.map(|(k, s)| (k, s.trim_end_matches(&['\r', '\n'][..]).to_string()))
+				.filter(|(k, s)| !s.is_empty() || *k != Kind::Code)
+				.collect()
+		})
+		.collect();
+	Some(lines)
+}
+
+fn kind_of(stack: &ScopeStack) -> Kind {
+	let scopes: Vec<String> = stack.as_slice().iter().map(|s| s.build_string()).collect();
+	if scopes
+		.iter()
+		.any(|s| s.starts_with("comment") && s.contains("documentation"))
+	{
+		Kind::Documentation
+	} else if scopes.iter().any(|s| s.starts_with("comment")) {
+		Kind::Comment
+	} else {
+		Kind::Code
+	}
+}
+
+fn text_of<F: Fn(Kind) -> bool>(segments: &Segments, predicate: F) -> String {
+	segments
+		.iter()
+		.filter(|(k, _)| predicate(*k))
+		.map(|(_, s)| s.as_str())
+		.collect()
+}
+
+fn is_documentation(segments: &Segments) -> bool {
+	segments.iter().any(|(k, _)| *k == Kind::Documentation)
+		&& segments
+			.iter()
+			.all(|(k, s)| *k == Kind::Documentation || s.trim().is_empty())
+}
+
+fn collapse(block: &[String]) -> Option<String> {
+	let first = block.first()?;
+	let indent = &first[..first.len() - first.trim_start().len()];
+	let opener: String = first
+		.trim_start()
+		.chars()
+		.take_while(|c| DOC_MARKERS.contains(*c))
+		.collect();
+	let summary = block
+		.iter()
+		.map(|l| l.trim_start_matches(|c: char| c.is_whitespace() || DOC_MARKERS.contains(c)))
+		.find(|l| l.chars().any(|c| c.is_alphanumeric()))
+		.unwrap_or("")
+		.trim_end();
+	let closer = DOC_OPENERS
+		.iter()
+		.find(|(o, _)| opener.starts_with(o))
+		.map(|(_, c)| *c);
+
+	Some(match closer {
+		Some(closer) => {
+			let summary = summary
+				.trim_end_matches(|c| DOC_MARKERS.contains(c))
+				.trim_end();
+			let separator = if opener.starts_with('/') { " " } else { "" };
+			format!("{}{}{}{}{}", indent, opener, separator, summary, closer)
+		}
+		None => format!("{}{} {}", indent, opener, summary),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	struct TextReader(&'static str, &'static str);
+
+	impl Reader for TextReader {
+		fn load(&self) -> Result<String> {
+			Ok(self.0.to_string())
+		}
+
+		fn source(&self) -> PathBuf {
+			PathBuf::from(self.1)
+		}
+	}
+
+	const RUST: &str = "/// Adds one.\n/// More detail.\nfn add(a: i32) -> i32 {\n    // increment\n    a + 1 // done\n}";
+	const PYTHON: &str =
+		"def f():\n    \"\"\"Summary line.\n\n    Details.\n    \"\"\"\n    return 1";
+
+	#[test]
+	fn strip_comments() {
+		let reader = TextReader(RUST, "a.rs");
+		assert_eq!(
+			ScopeReader::new(&reader, ScopeFilter::StripComments)
+				.load()
+				.unwrap(),
+			"fn add(a: i32) -> i32 {\n    a + 1\n}"
+		);
+	}
+
+	#[test]
+	fn comments_only() {
+		let reader = TextReader(RUST, "a.rs");
+		assert_eq!(
+			ScopeReader::new(&reader, ScopeFilter::CommentsOnly)
+				.load()
+				.unwrap(),
+			"/// Adds one.\n/// More detail.\n// increment\n// done"
+		);
+	}
+
+	#[test]
+	fn collapse_docs() {
+		let reader = TextReader(RUST, "a.rs");
+		assert!(ScopeReader::new(&reader, ScopeFilter::CollapseDocs)
+			.load()
+			.unwrap()
+			.starts_with("/// Adds one.\nfn add"));
+
+		let reader = TextReader(PYTHON, "a.py");
+		assert_eq!(
+			ScopeReader::new(&reader, ScopeFilter::CollapseDocs)
+				.load()
+				.unwrap(),
+			"def f():\n    \"\"\"Summary line.\"\"\"\n    return 1"
+		);
+	}
+
+	#[test]
+	fn unknown_extension() {
+		let reader = TextReader("plain text", "a.unknown");
+		assert_eq!(
+			ScopeReader::new(&reader, ScopeFilter::StripComments)
+				.load()
+				.unwrap(),
+			"plain text"
+		);
+	}
+
+	#[test]
+	fn empty_after_filter() {
+		let reader = TextReader("fn main() {}", "a.rs");
+		assert!(ScopeReader::new(&reader, ScopeFilter::CommentsOnly)
+			.load()
+			.is_err());
+	}
+}
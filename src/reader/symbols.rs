@@ -0,0 +1,144 @@
+use crate::reader::reader::Reader;
+use crate::reader::words::{capitalize, wrap, WordList, LINE_WIDTH};
+use anyhow::{anyhow, Result};
+use rand::prelude::*;
+use std::path::PathBuf;
+
+const OPERATORS: [&str; 32] = [
+	"->", "=>", "::", "==", "!=", "<=", ">=", "&&", "||", "+=", "-=", "*=", "/=", "%=", "<<", ">>",
+	">>=", "<<=", "&=", "|=", "^=", "..", "..=", "?", "!", "&", "|", "^", "+", "-", "*", "=",
+];
+const BRACKETS: [(&str, &str); 4] = [("(", ")"), ("[", "]"), ("{", "}"), ("<", ">")];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Density {
+	symbols: f64,
+	literals: f64,
+}
+
+pub struct SymbolsReader {
+	count: usize,
+	density: Density,
+}
+
+impl Density {
+	pub fn new(symbols: usize, literals: usize) -> Result<Self> {
+		if symbols + literals > 100 {
+			Err(anyhow!(
+				"Symbol and literal density must add up to at most 100%."
+			))
+		} else {
+			Ok(Density {
+				symbols: symbols as f64 / 100.0,
+				literals: literals as f64 / 100.0,
+			})
+		}
+	}
+}
+
+impl SymbolsReader {
+	pub fn new(count: usize, density: Density) -> Self {
+		SymbolsReader { count, density }
+	}
+
+	pub fn generate<R: Rng>(&self, rng: &mut R) -> String {
+		let words = WordList::English200.words();
+		let tokens: Vec<String> = (0..self.count)
+			.map(|_| {
+				let roll: f64 = rng.gen();
+				if roll < self.density.symbols {
+					SymbolsReader::symbol(rng, &words)
+				} else if roll < self.density.symbols + self.density.literals {
+					SymbolsReader::literal(rng, &words)
+				} else {
+					SymbolsReader::identifier(rng, &words)
+				}
+			})
+			.collect();
+		wrap(&tokens, LINE_WIDTH)
+	}
+
+	fn identifier<R: Rng>(rng: &mut R, words: &[&str]) -> String {
+		let parts: Vec<&str> = (0..rng.gen_range(1..=3))
+			.filter_map(|_| words.choose(rng).copied())
+			.collect();
+		match rng.gen_range(0..3) {
+			0 => parts.join("_"),
+			1 => parts
+				.iter()
+				.enumerate()
+				.map(|(i, p)| if i == 0 { p.to_string() } else { capitalize(p) })
+				.collect(),
+			_ => parts.iter().map(|p| capitalize(p)).collect(),
+		}
+	}
+
+	fn literal<R: Rng>(rng: &mut R, words: &[&str]) -> String {
+		match rng.gen_range(0..6) {
+			0 => format!("\"{}\"", words.choose(rng).unwrap_or(&"")),
+			1 => format!("'{}'", (b'a' + rng.gen_range(0..26)) as char),
+			2 => format!("0x{:x}", rng.gen_range(0..0x1000)),
+			3 => format!("{}.{}", rng.gen_range(0..100), rng.gen_range(0..100)),
+			4 => format!("{}_000", rng.gen_range(1..1000)),
+			_ => rng.gen_range(0..1000).to_string(),
+		}
+	}
+
+	fn symbol<R: Rng>(rng: &mut R, words: &[&str]) -> String {
+		if rng.gen_bool(0.5) {
+			OPERATORS.choose(rng).unwrap_or(&"=").to_string()
+		} else {
+			let (open, close) = BRACKETS.choose(rng).unwrap_or(&("(", ")"));
+			let inner = if rng.gen_bool(0.5) {
+				SymbolsReader::identifier(rng, words)
+			} else {
+				SymbolsReader::literal(rng, words)
+			};
+			format!("{}{}{}", open, inner, close)
+		}
+	}
+}
+
+impl Reader for SymbolsReader {
+	fn load(&self) -> Result<String> {
+		Ok(self.generate(&mut rand::thread_rng()))
+	}
+
+	fn source(&self) -> PathBuf {
+		PathBuf::from("symbols.drill")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::rngs::StdRng;
+
+	#[test]
+	fn density() {
+		assert!(Density::new(60, 50).is_err());
+		assert!(Density::new(50, 50).is_ok());
+	}
+
+	#[test]
+	fn identifiers_only() {
+		let reader = SymbolsReader::new(100, Density::new(0, 0).unwrap());
+		let text = reader.generate(&mut StdRng::seed_from_u64(1));
+
+		assert_eq!(text.split_whitespace().count(), 100);
+		assert!(text
+			.split_whitespace()
+			.all(|t| t.chars().all(|c| c.is_ascii_alphabetic() || c == '_')));
+	}
+
+	#[test]
+	fn symbols_only() {
+		let reader = SymbolsReader::new(100, Density::new(100, 0).unwrap());
+		let text = reader.generate(&mut StdRng::seed_from_u64(1));
+
+		assert!(text.split_whitespace().all(|t| OPERATORS.contains(&t)
+			|| BRACKETS
+				.iter()
+				.any(|(o, c)| t.starts_with(o) && t.ends_with(c))));
+	}
+}
@@ -0,0 +1,225 @@
+use crate::reader::reader::Reader;
+use anyhow::{anyhow, Result};
+use rand::prelude::*;
+use std::path::PathBuf;
+
+pub const LINE_WIDTH: usize = 60;
+
+const ENGLISH_200: &str = include_str!("../../res/words/english_200.txt");
+const ENGLISH_1K: &str = include_str!("../../res/words/english_1k.txt");
+const ENGLISH_10K: &str = include_str!("../../res/words/english_10k.txt");
+const KEYWORDS: [(&str, &str); 10] = [
+	("c", include_str!("../../res/keywords/c.txt")),
+	("cpp", include_str!("../../res/keywords/cpp.txt")),
+	("go", include_str!("../../res/keywords/go.txt")),
+	("java", include_str!("../../res/keywords/java.txt")),
+	("js", include_str!("../../res/keywords/js.txt")),
+	("py", include_str!("../../res/keywords/py.txt")),
+	("rb", include_str!("../../res/keywords/rb.txt")),
+	("rs", include_str!("../../res/keywords/rs.txt")),
+	("sh", include_str!("../../res/keywords/sh.txt")),
+	("ts", include_str!("../../res/keywords/ts.txt")),
+];
+
+const SENTENCE_END_RATE: f64 = 0.1;
+const COMMA_RATE: f64 = 0.1;
+const QUOTE_RATE: f64 = 0.03;
+const NUMBER_RATE: f64 = 0.1;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WordList {
+	English200,
+	English1k,
+	English10k,
+	Keywords(String),
+}
+
+pub struct WordsReader {
+	list: WordList,
+	count: usize,
+	punctuation: bool,
+	numbers: bool,
+}
+
+impl WordList {
+	pub fn parse(name: &str) -> Result<Self> {
+		match name.to_lowercase().as_str() {
+			"200" => Ok(WordList::English200),
+			"1k" | "1000" => Ok(WordList::English1k),
+			"10k" | "10000" => Ok(WordList::English10k),
+			_ => Err(anyhow!(
+				"Unknown word list: {} (expected 200, 1k or 10k).",
+				name
+			)),
+		}
+	}
+
+	pub fn keywords(extension: &str) -> Result<Self> {
+		let extension = extension.to_lowercase();
+		if KEYWORDS.iter().any(|(e, _)| *e == extension) {
+			Ok(WordList::Keywords(extension))
+		} else {
+			Err(anyhow!(
+				"No keywords for: {} (available: {}).",
+				extension,
+				KEYWORDS
+					.iter()
+					.map(|(e, _)| *e)
+					.collect::<Vec<&str>>()
+					.join(", ")
+			))
+		}
+	}
+
+	pub fn words(&self) -> Vec<&'static str> {
+		let text = match self {
+			WordList::English200 => ENGLISH_200,
+			WordList::English1k => ENGLISH_1K,
+			WordList::English10k => ENGLISH_10K,
+			WordList::Keywords(e) => KEYWORDS
+				.iter()
+				.find(|(k, _)| k == e)
+				.map(|(_, t)| *t)
+				.unwrap_or(""),
+		};
+		text.split_whitespace().collect()
+	}
+
+	fn source(&self) -> PathBuf {
+		PathBuf::from(match self {
+			WordList::English200 => "200.words".to_string(),
+			WordList::English1k => "1k.words".to_string(),
+			WordList::English10k => "10k.words".to_string(),
+			WordList::Keywords(e) => format!("{}.keywords", e),
+		})
+	}
+}
+
+impl WordsReader {
+	pub fn new(list: WordList, count: usize, punctuation: bool, numbers: bool) -> Self {
+		WordsReader {
+			list,
+			count,
+			punctuation,
+			numbers,
+		}
+	}
+
+	pub fn generate<R: Rng>(&self, rng: &mut R) -> String {
+		let words = self.list.words();
+		let mut sentence_start = self.punctuation;
+		let picked: Vec<String> = (0..self.count)
+			.filter_map(|i| {
+				let word = if self.numbers && rng.gen_bool(NUMBER_RATE) {
+					rng.gen_range(0..10000).to_string()
+				} else {
+					words.choose(rng)?.to_string()
+				};
+				if !self.punctuation {
+					return Some(word);
+				}
+
+				let word = if sentence_start {
+					sentence_start = false;
+					capitalize(&word)
+				} else {
+					word
+				};
+				let word = if rng.gen_bool(QUOTE_RATE) {
+					format!("\"{}\"", word)
+				} else {
+					word
+				};
+				Some(if i + 1 == self.count || rng.gen_bool(SENTENCE_END_RATE) {
+					sentence_start = true;
+					word + *[".", ".", ".", "?", "!"].choose(rng).unwrap()
+				} else if rng.gen_bool(COMMA_RATE) {
+					word + *[",", ",", ";", ":"].choose(rng).unwrap()
+				} else {
+					word
+				})
+			})
+			.collect();
+		wrap(&picked, LINE_WIDTH)
+	}
+}
+
+impl Reader for WordsReader {
+	fn load(&self) -> Result<String> {
+		Ok(self.generate(&mut rand::thread_rng()))
+	}
+
+	fn source(&self) -> PathBuf {
+		self.list.source()
+	}
+}
+
+pub fn capitalize(word: &str) -> String {
+	let mut chars = word.chars();
+	chars
+		.next()
+		.map(|c| c.to_uppercase().collect::<String>() + chars.as_str())
+		.unwrap_or_default()
+}
+
+pub fn wrap<S: AsRef<str>>(words: &[S], width: usize) -> String {
+	let mut lines: Vec<String> = Vec::new();
+	let mut line = String::new();
+	for word in words.iter().map(|w| w.as_ref()) {
+		if !line.is_empty() && line.len() + word.len() + 1 > width {
+			lines.push(line);
+			line = String::new();
+		}
+		if !line.is_empty() {
+			line.push(' ');
+		}
+		line.push_str(word);
+	}
+	if !line.is_empty() {
+		lines.push(line);
+	}
+	lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::rngs::StdRng;
+
+	#[test]
+	fn word_lists() {
+		assert_eq!(WordList::English200.words().len(), 200);
+		assert_eq!(WordList::English1k.words().len(), 1000);
+		assert_eq!(WordList::English10k.words().len(), 10000);
+		assert!(WordList::keywords("rs").unwrap().words().contains(&"fn"));
+		assert!(WordList::keywords("cobol").is_err());
+		assert!(WordList::parse("5k").is_err());
+	}
+
+	#[test]
+	fn generate() {
+		let reader = WordsReader::new(WordList::English200, 50, false, false);
+		let text = reader.generate(&mut StdRng::seed_from_u64(1));
+		let words = WordList::English200.words();
+
+		assert_eq!(text.split_whitespace().count(), 50);
+		assert!(text.split_whitespace().all(|w| words.contains(&w)));
+		assert!(text.lines().all(|l| l.len() <= LINE_WIDTH));
+	}
+
+	#[test]
+	fn generate_with_punctuation() {
+		let reader = WordsReader::new(WordList::English200, 50, true, true);
+		let text = reader.generate(&mut StdRng::seed_from_u64(1));
+
+		assert_eq!(text.split_whitespace().count(), 50);
+		assert!(text.chars().next().unwrap().is_uppercase() || text.starts_with('"'));
+		assert!(text.ends_with(|c| ".?!\"".contains(c)));
+	}
+
+	#[test]
+	fn wrap_lines() {
+		assert_eq!(wrap(&["ab", "cd", "ef"], 5), "ab cd\nef");
+		assert_eq!(wrap(&["abcdef"], 3), "abcdef");
+	}
+}
@@ -1,2 +1,10 @@
+pub mod analysis;
+pub mod cycle;
+pub mod keystroke;
 pub mod line;
+pub mod motion;
+pub mod navigation;
+pub mod policy;
+pub mod start;
+pub mod text;
 pub mod typing;
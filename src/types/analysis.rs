@@ -0,0 +1,184 @@
+use crate::types::keystroke::Keystroke;
+use std::collections::BTreeMap;
+
+const MIN_WORD_LENGTH: usize = 2;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WordSpeed {
+	pub word: String,
+	pub line_no: usize,
+	pub wpm: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pause {
+	pub millis: u64,
+	pub line_no: usize,
+	/// The line with a marker where the pause happened.
+	pub context: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineSpeed {
+	pub line_no: usize,
+	pub wpm: usize,
+	pub typo: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Analysis {
+	pub slowest_words: Vec<WordSpeed>,
+	pub longest_pauses: Vec<Pause>,
+	pub lines: Vec<LineSpeed>,
+}
+
+impl Analysis {
+	/// Splits the time between keystrokes over the words and lines they were typed on.
+	/// `lines` is the full text of each line, indexed by `line_no - 1`.
+	pub fn new(keystrokes: &[Keystroke], lines: &[String], limit: usize) -> Self {
+		let intervals: Vec<(&Keystroke, u64)> = keystrokes
+			.iter()
+			.scan(0, |prev, k| {
+				let interval = k.at.saturating_sub(*prev);
+				*prev = k.at;
+				Some((k, interval))
+			})
+			.collect();
+
+		let mut words: BTreeMap<(usize, usize), (String, u64)> = BTreeMap::new();
+		let mut per_line: BTreeMap<usize, (usize, usize, u64)> = BTreeMap::new();
+		for (k, interval) in &intervals {
+			let line = lines.get(k.line_no.wrapping_sub(1)).map(|l| l.as_str());
+			if let Some((start, word)) = line.and_then(|l| word_at(l, k.column)) {
+				let entry = words.entry((k.line_no, start)).or_insert((word, 0));
+				entry.1 += interval;
+			}
+
+			let entry = per_line.entry(k.line_no).or_insert((0, 0, 0));
+			if k.correct {
+				entry.0 += 1;
+			} else {
+				entry.1 += 1;
+			}
+			entry.2 += interval;
+		}
+
+		let mut slowest_words: Vec<WordSpeed> = words
+			.into_iter()
+			.filter(|(_, (word, _))| word.chars().count() >= MIN_WORD_LENGTH)
+			.map(|((line_no, _), (word, millis))| WordSpeed {
+				wpm: wpm(word.chars().count(), millis),
+				word,
+				line_no,
+			})
+			.collect();
+		slowest_words.sort_by_key(|w| w.wpm);
+		slowest_words.truncate(limit);
+
+		let mut pauses: Vec<&(&Keystroke, u64)> = intervals.iter().skip(1).collect();
+		pauses.sort_by_key(|(_, interval)| std::cmp::Reverse(*interval));
+		let longest_pauses = pauses
+			.into_iter()
+			.take(limit)
+			.map(|(k, millis)| Pause {
+				millis: *millis,
+				line_no: k.line_no,
+				context: lines
+					.get(k.line_no.wrapping_sub(1))
+					.map(|l| mark(l, k.column))
+					.unwrap_or_default(),
+			})
+			.collect();
+
+		Analysis {
+			slowest_words,
+			longest_pauses,
+			lines: per_line
+				.into_iter()
+				.map(|(line_no, (typed, typo, millis))| LineSpeed {
+					line_no,
+					wpm: wpm(typed + typo, millis),
+					typo,
+				})
+				.collect(),
+		}
+	}
+}
+
+fn wpm(chars: usize, millis: u64) -> usize {
+	(chars as u64 * 60_000 / 5 / millis.max(1)) as usize
+}
+
+/// The word around `column` and where it starts, or None on whitespace.
+fn word_at(line: &str, column: usize) -> Option<(usize, String)> {
+	let chars: Vec<char> = line.chars().collect();
+	if chars.get(column).map(|c| c.is_whitespace()).unwrap_or(true) {
+		return None;
+	}
+	let start = chars[..column]
+		.iter()
+		.rposition(|c| c.is_whitespace())
+		.map(|i| i + 1)
+		.unwrap_or(0);
+	let end = chars[column..]
+		.iter()
+		.position(|c| c.is_whitespace())
+		.map(|i| i + column)
+		.unwrap_or(chars.len());
+	Some((start, chars[start..end].iter().collect()))
+}
+
+fn mark(line: &str, column: usize) -> String {
+	let chars: Vec<char> = line.trim_end().chars().collect();
+	let column = column.min(chars.len());
+	let before: String = chars[..column].iter().collect();
+	let after: String = chars[column..].iter().collect();
+	format!("{}|{}", before.trim_start(), after)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn key(at: u64, line_no: usize, column: usize, correct: bool) -> Keystroke {
+		Keystroke {
+			at,
+			key: 'x',
+			expected: None,
+			correct,
+			line_no,
+			column,
+		}
+	}
+
+	#[test]
+	fn word_at() {
+		assert_eq!(super::word_at("  let foo", 7), Some((6, "foo".to_string())));
+		assert_eq!(super::word_at("  let foo", 5), None);
+		assert_eq!(super::word_at("  let foo", 2), Some((2, "let".to_string())));
+	}
+
+	#[test]
+	fn analysis() {
+		let lines = vec!["ab cd".to_string(), "ef".to_string()];
+		let keystrokes = vec![
+			key(100, 1, 0, true),
+			key(200, 1, 1, true),
+			key(300, 1, 2, true),
+			key(2300, 1, 3, false),
+			key(2400, 1, 3, true),
+			key(2500, 1, 4, true),
+			key(2600, 2, 0, true),
+			key(2700, 2, 1, true),
+		];
+		let analysis = Analysis::new(&keystrokes, &lines, 2);
+
+		assert_eq!(analysis.slowest_words[0].word, "cd");
+		assert_eq!(analysis.slowest_words[1].word, "ab");
+		assert_eq!(analysis.longest_pauses[0].millis, 2000);
+		assert_eq!(analysis.longest_pauses[0].context, "ab |cd");
+		assert_eq!(analysis.lines.len(), 2);
+		assert_eq!(analysis.lines[0].typo, 1);
+		assert!(analysis.lines[1].wpm > analysis.lines[0].wpm);
+	}
+}
@@ -0,0 +1,52 @@
+/// A sorted list of unique values that wraps around at both ends.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cycle<T> {
+	items: Vec<T>,
+}
+
+impl<T: Ord + Clone> Cycle<T> {
+	pub fn new(mut items: Vec<T>) -> Self {
+		items.sort();
+		items.dedup();
+		Cycle { items }
+	}
+
+	pub fn items(&self) -> &[T] {
+		&self.items
+	}
+
+	/// The smallest item after `current`, wrapping to the first one.
+	pub fn next(&self, current: &T) -> Option<T> {
+		self.items
+			.iter()
+			.find(|i| *i > current)
+			.or_else(|| self.items.first())
+			.cloned()
+	}
+
+	/// The largest item before `current`, wrapping to the last one.
+	pub fn prev(&self, current: &T) -> Option<T> {
+		self.items
+			.iter()
+			.rev()
+			.find(|i| *i < current)
+			.or_else(|| self.items.last())
+			.cloned()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn cycle() {
+		let cycle = Cycle::new(vec![30, 15, 60, 30]);
+		assert_eq!(cycle.items(), &[15, 30, 60]);
+		assert_eq!(cycle.next(&30), Some(60));
+		assert_eq!(cycle.next(&60), Some(15));
+		assert_eq!(cycle.prev(&15), Some(60));
+		assert_eq!(cycle.next(&20), Some(30));
+		assert_eq!(Cycle::<usize>::new(vec![]).next(&1), None);
+	}
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Keystroke {
+	/// Milliseconds since the test started.
+	pub at: u64,
+	pub key: char,
+	pub expected: Option<char>,
+	pub correct: bool,
+	#[serde(default)]
+	pub line_no: usize,
+	/// Char position of the cursor within the line, leading spaces included.
+	#[serde(default)]
+	pub column: usize,
+}
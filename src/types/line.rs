@@ -1,70 +1,79 @@
-#[derive(Clone, Debug)]
-pub struct Line {
+/// One line of the text and how far into it the typist is, borrowed from the
+/// typing state so drawing it copies nothing until the spans are built.
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
 	line_no: usize,
-	head_space: Option<String>,
-	entered: Option<String>,
-	current: Option<char>,
-	rest: Option<String>,
+	chars: &'a [char],
+	/// Index of the char to type next; `chars.len()` once the line is entered.
+	cursor: usize,
+	/// Where the line starts in the text, which `errors` are relative to.
+	offset: usize,
+	errors: &'a [usize],
 }
 
-impl Line {
-	fn start_index(line: &str) -> usize {
-		line.len() - line.trim_start().len()
+/// The closer an editor inserts after typing `c`.
+fn closer(c: char) -> Option<char> {
+	match c {
+		'(' => Some(')'),
+		'[' => Some(']'),
+		'{' => Some('}'),
+		'"' => Some('"'),
+		_ => None,
 	}
+}
 
-	pub fn new(line_no: usize, line: &str) -> Self {
-		let text = &line[Line::start_index(line)..];
-		let head_space = &line[0..Line::start_index(line)];
-		let head_space = if head_space.is_empty() {
-			None
-		} else {
-			Some(head_space.to_string())
-		};
-
-		match text.chars().collect::<Vec<char>>().as_slice() {
-			[h] => Line {
-				line_no: line_no,
-				head_space: head_space,
-				entered: None,
-				current: Some(h.clone()),
-				rest: None,
-			},
-			[h, rest @ ..] => Line {
-				line_no: line_no,
-				head_space: head_space,
-				entered: None,
-				current: Some(h.clone()),
-				rest: Some(String::from_iter(rest)),
-			},
-			_ => Line {
-				line_no: line_no,
-				head_space: None,
-				entered: None,
-				current: None,
-				rest: None,
-			},
+impl<'a> Line<'a> {
+	/// A line nobody has typed on yet, with the cursor past its indentation.
+	pub fn new(line_no: usize, chars: &'a [char]) -> Self {
+		Line {
+			line_no,
+			chars,
+			cursor: Line::indent(chars),
+			offset: 0,
+			errors: &[],
 		}
 	}
 
+	/// The number of leading whitespace chars, which are never typed.
+	pub fn indent(chars: &[char]) -> usize {
+		chars
+			.iter()
+			.position(|c| !c.is_whitespace())
+			.unwrap_or(chars.len())
+	}
+
+	pub fn with_cursor(mut self, cursor: usize) -> Self {
+		self.cursor = cursor.min(self.chars.len());
+		self
+	}
+
+	/// Mistyped positions, counted from `offset` in the text.
+	pub fn with_errors(mut self, offset: usize, errors: &'a [usize]) -> Self {
+		self.offset = offset;
+		self.errors = errors;
+		self
+	}
+
 	pub fn current_text(&self) -> Option<char> {
-		self.current
+		self.chars.get(self.cursor).copied()
 	}
 
 	pub fn entered_text(&self) -> Option<String> {
-		match (self.head_space.clone(), self.entered.clone()) {
-			(Some(h), Some(entered)) => Some(h + &entered),
-			(Some(h), None) => Some(h),
-			(None, Some(entered)) => Some(entered),
-			_ => None,
+		if self.cursor == 0 {
+			None
+		} else {
+			Some(self.chars[..self.cursor].iter().collect())
 		}
 	}
 
 	pub fn rest_text(&self) -> Option<String> {
-		self.rest.clone()
+		self.chars
+			.get(self.cursor + 1..)
+			.map(|rest| rest.iter().collect())
 	}
 
 	pub fn input(&self, c: char) -> bool {
-		match self.current {
+		match self.current_text() {
 			Some(i) => i == c,
 			None => true,
 		}
@@ -75,36 +84,53 @@ impl Line {
 	}
 
 	pub fn is_entered(&self) -> bool {
-		self.rest.is_none()
-	}
-
-	pub fn next(&self) -> Self {
-		if let Some(rest) = self.rest.clone() {
-			match rest.chars().collect::<Vec<char>>().as_slice() {
-				[h, rest @ ..] => Line {
-					line_no: self.line_no,
-					head_space: self.head_space.clone(),
-					entered: match self.entered.clone() {
-						Some(e) => self.current.map(|c| e + String::from(c).as_str()),
-						None => self.current.map(String::from),
-					},
-					current: Some(h.clone()),
-					rest: Some(String::from_iter(rest)),
-				},
-				_ => Line {
-					line_no: self.line_no,
-					head_space: self.head_space.clone(),
-					entered: match self.entered.clone() {
-						Some(e) => self.current.map(|c| e + String::from(c).as_str()),
-						None => self.current.map(String::from),
-					},
-					current: None,
-					rest: None,
-				},
+		self.cursor >= self.chars.len()
+	}
+
+	/// Where the word being typed starts, never before the indentation.
+	pub fn word_start(&self) -> usize {
+		let indent = Line::indent(self.chars).min(self.cursor);
+		self.chars[indent..self.cursor]
+			.iter()
+			.rposition(|c| c.is_whitespace())
+			.map(|i| indent + i + 1)
+			.unwrap_or(indent)
+	}
+
+	/// Position of the closer matching the current char, if it's an opener.
+	pub fn matching_closer(&self) -> Option<usize> {
+		let open = self.current_text()?;
+		let close = closer(open)?;
+		let rest = &self.chars[self.cursor + 1..];
+		let mut depth = 0;
+		for (i, c) in rest.iter().enumerate() {
+			if open == close {
+				if *c == close && (i == 0 || rest[i - 1] != '\\') {
+					return Some(self.cursor + 1 + i);
+				}
+			} else if *c == open {
+				depth += 1;
+			} else if *c == close {
+				if depth == 0 {
+					return Some(self.cursor + 1 + i);
+				}
+				depth -= 1;
 			}
-		} else {
-			self.clone()
 		}
+		None
+	}
+
+	pub fn text(&self) -> String {
+		self.chars.iter().collect()
+	}
+
+	pub fn cursor(&self) -> usize {
+		self.cursor
+	}
+
+	/// Positions of mistyped chars within `entered_text`.
+	pub fn error_indices(&self) -> Vec<usize> {
+		self.errors.iter().map(|i| i - self.offset).collect()
 	}
 }
 
@@ -112,68 +138,78 @@ impl Line {
 mod tests {
 	use super::*;
 
+	fn chars(s: &str) -> Vec<char> {
+		s.chars().collect()
+	}
+
 	#[test]
 	fn new() {
-		let line = Line::new(1, "    {");
-		assert_eq!(line.head_space.unwrap(), "    ");
+		let chars = chars("    {");
+		let line = Line::new(1, &chars);
+		assert_eq!(line.entered_text().unwrap(), "    ");
+		assert_eq!(line.current_text(), Some('{'));
+		assert_eq!(line.rest_text().unwrap(), "");
 	}
 
 	#[test]
 	fn has_next() {
-		let input = Line::new(1, "      input test");
-		assert_eq!(
-			input
-				.clone()
-				.current_text()
-				.map(String::from)
-				.unwrap_or("".to_owned()),
-			"i"
-		);
-
-		let next_input = input.next();
-
-		assert_eq!(
-			&next_input.head_space.clone().unwrap(),
-			&"      ".to_string()
-		);
-		assert_eq!(
-			&next_input
-				.current_text()
-				.map(String::from)
-				.unwrap_or("".to_owned()),
-			&"n"
-		);
-		assert_eq!(&next_input.entered_text().unwrap(), &"      i".to_string());
-		assert_eq!(&next_input.rest_text().unwrap(), &"put test".to_string());
+		let chars = chars("      input test");
+		let line = Line::new(1, &chars);
+		assert_eq!(line.current_text(), Some('i'));
+
+		let next = line.with_cursor(line.cursor() + 1);
+		assert_eq!(next.current_text(), Some('n'));
+		assert_eq!(next.entered_text().unwrap(), "      i");
+		assert_eq!(next.rest_text().unwrap(), "put test");
+	}
+
+	#[test]
+	fn entered() {
+		let chars = chars("i");
+		let line = Line::new(0, &chars);
+		assert_eq!(line.current_text(), Some('i'));
+		assert!(line.with_cursor(1).is_entered());
+		assert_eq!(line.with_cursor(5).cursor(), 1);
+
+		let blank: Vec<char> = Vec::new();
+		assert!(Line::new(0, &blank).is_entered());
+		assert_eq!(Line::new(0, &blank).current_text(), None);
+	}
+
+	#[test]
+	fn errors() {
+		let chars = chars("  abc");
+		let errors = [10, 13];
+		let line = Line::new(1, &chars).with_cursor(4).with_errors(10, &errors);
+		assert_eq!(line.entered_text().unwrap(), "  ab");
+		assert_eq!(line.error_indices(), vec![0, 3]);
+	}
+
+	#[test]
+	fn word_start() {
+		let chars = chars("  ab cd");
+		let line = Line::new(1, &chars);
+		assert_eq!(line.with_cursor(6).word_start(), 5);
+		assert_eq!(line.with_cursor(5).word_start(), 5);
+		assert_eq!(line.with_cursor(3).word_start(), 2);
 	}
 
 	#[test]
-	fn no_next() {
-		let input = Line::new(0, "i");
-		assert_eq!(
-			input
-				.clone()
-				.current_text()
-				.map(String::from)
-				.unwrap_or("".to_owned()),
-			"i"
-		);
-		let next_input = input.next();
-		assert!(next_input.is_entered());
+	fn text() {
+		let chars = chars("  ab cd");
+		let line = Line::new(1, &chars).with_cursor(4);
+		assert_eq!(line.text(), "  ab cd");
+		assert_eq!(line.cursor(), 4);
 	}
 
 	#[test]
-	fn new_line_only() {
-		let input = Line::new(0, "\n");
-		assert_eq!(
-			input
-				.clone()
-				.current_text()
-				.map(String::from)
-				.unwrap_or("".to_owned()),
-			""
-		);
-		let next_input = input.next();
-		assert!(next_input.is_entered());
+	fn matching_closer() {
+		let chars = chars("f(a[0], \"x\\\"\") {");
+		let line = Line::new(1, &chars);
+		assert_eq!(line.with_cursor(1).matching_closer(), Some(13));
+		assert_eq!(line.with_cursor(3).matching_closer(), Some(5));
+		assert_eq!(line.with_cursor(8).matching_closer(), Some(12));
+		assert_eq!(line.with_cursor(15).matching_closer(), None);
+		assert_eq!(line.with_cursor(0).matching_closer(), None);
 	}
 }
@@ -0,0 +1,301 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Row and column of a char in the snippet.
+pub type Position = (usize, usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Motion {
+	Left,
+	Down,
+	Up,
+	Right,
+	WordStart,
+	WordBack,
+	WordEnd,
+	Find(char),
+	Till(char),
+	Top,
+	Bottom,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Class {
+	Empty,
+	Space,
+	Keyword,
+	Punctuation,
+}
+
+impl Motion {
+	/// The motion typed by `keys`, once they are complete.
+	pub fn parse(keys: &[char]) -> Option<Self> {
+		match keys {
+			['h'] => Some(Motion::Left),
+			['j'] => Some(Motion::Down),
+			['k'] => Some(Motion::Up),
+			['l'] => Some(Motion::Right),
+			['w'] => Some(Motion::WordStart),
+			['b'] => Some(Motion::WordBack),
+			['e'] => Some(Motion::WordEnd),
+			['f', c] => Some(Motion::Find(*c)),
+			['t', c] => Some(Motion::Till(*c)),
+			['g', 'g'] => Some(Motion::Top),
+			['G'] => Some(Motion::Bottom),
+			_ => None,
+		}
+	}
+
+	/// Whether `keys` start a motion that needs another key.
+	pub fn is_prefix(keys: &[char]) -> bool {
+		matches!(keys, ['f'] | ['t'] | ['g'])
+	}
+
+	pub fn keys(&self) -> usize {
+		match self {
+			Motion::Find(_) | Motion::Till(_) | Motion::Top => 2,
+			_ => 1,
+		}
+	}
+
+	pub fn apply(&self, text: &[Vec<char>], pos: Position) -> Position {
+		let (row, col) = pos;
+		match self {
+			Motion::Left => (row, col.saturating_sub(1)),
+			Motion::Right => (row, clamp_col(text, row, col + 1)),
+			Motion::Up if row > 0 => (row - 1, clamp_col(text, row - 1, col)),
+			Motion::Down if row + 1 < text.len() => (row + 1, clamp_col(text, row + 1, col)),
+			Motion::Up | Motion::Down => pos,
+			Motion::WordStart => word_start(text, pos),
+			Motion::WordBack => word_back(text, pos),
+			Motion::WordEnd => word_end(text, pos),
+			Motion::Find(c) => find(text, pos, *c).unwrap_or(pos),
+			Motion::Till(c) => find(text, pos, *c)
+				.map(|(r, i)| (r, i - 1))
+				.filter(|(_, i)| *i > col)
+				.unwrap_or(pos),
+			Motion::Top => (0, first_non_blank(text, 0)),
+			Motion::Bottom => {
+				let last = text.len().saturating_sub(1);
+				(last, first_non_blank(text, last))
+			}
+		}
+	}
+}
+
+/// The fewest keys that move the cursor from `from` to `to`.
+pub fn optimal(text: &[Vec<char>], from: Position, to: Position) -> Option<usize> {
+	let mut costs: HashMap<Position, usize> = HashMap::new();
+	let mut queue = BinaryHeap::new();
+	costs.insert(from, 0);
+	queue.push(Reverse((0, from)));
+
+	while let Some(Reverse((cost, pos))) = queue.pop() {
+		if pos == to {
+			return Some(cost);
+		}
+		if costs.get(&pos).is_some_and(|c| *c < cost) {
+			continue;
+		}
+		for motion in motions(text, pos) {
+			let next = motion.apply(text, pos);
+			let next_cost = cost + motion.keys();
+			if costs.get(&next).map_or(true, |c| next_cost < *c) {
+				costs.insert(next, next_cost);
+				queue.push(Reverse((next_cost, next)));
+			}
+		}
+	}
+	None
+}
+
+/// Every motion worth trying from `pos`: the fixed ones, and f/t to each char later on the row.
+fn motions(text: &[Vec<char>], pos: Position) -> Vec<Motion> {
+	let mut motions = vec![
+		Motion::Left,
+		Motion::Down,
+		Motion::Up,
+		Motion::Right,
+		Motion::WordStart,
+		Motion::WordBack,
+		Motion::WordEnd,
+		Motion::Top,
+		Motion::Bottom,
+	];
+	let mut chars: Vec<char> = text[pos.0].iter().skip(pos.1 + 1).cloned().collect();
+	chars.sort_unstable();
+	chars.dedup();
+	for c in chars {
+		motions.push(Motion::Find(c));
+		motions.push(Motion::Till(c));
+	}
+	motions
+}
+
+fn clamp_col(text: &[Vec<char>], row: usize, col: usize) -> usize {
+	col.min(text[row].len().saturating_sub(1))
+}
+
+fn first_non_blank(text: &[Vec<char>], row: usize) -> usize {
+	text.get(row)
+		.and_then(|line| line.iter().position(|c| !c.is_whitespace()))
+		.unwrap_or(0)
+}
+
+fn find(text: &[Vec<char>], (row, col): Position, c: char) -> Option<Position> {
+	text[row]
+		.iter()
+		.enumerate()
+		.skip(col + 1)
+		.find(|(_, x)| **x == c)
+		.map(|(i, _)| (row, i))
+}
+
+fn class(text: &[Vec<char>], (row, col): Position) -> Class {
+	match text[row].get(col) {
+		None => Class::Empty,
+		Some(c) if c.is_whitespace() => Class::Space,
+		Some(c) if c.is_alphanumeric() || *c == '_' => Class::Keyword,
+		Some(_) => Class::Punctuation,
+	}
+}
+
+/// The next position, wrapping to the start of the following row.
+fn forward(text: &[Vec<char>], (row, col): Position) -> Option<Position> {
+	if col + 1 < text[row].len() {
+		Some((row, col + 1))
+	} else if row + 1 < text.len() {
+		Some((row + 1, 0))
+	} else {
+		None
+	}
+}
+
+fn backward(text: &[Vec<char>], (row, col): Position) -> Option<Position> {
+	if col > 0 {
+		Some((row, col - 1))
+	} else if row > 0 {
+		Some((row - 1, text[row - 1].len().saturating_sub(1)))
+	} else {
+		None
+	}
+}
+
+fn word_start(text: &[Vec<char>], pos: Position) -> Position {
+	let start = class(text, pos);
+	let mut cur = pos;
+	loop {
+		match forward(text, cur) {
+			None => return cur,
+			Some(next) => {
+				let same = next.0 == cur.0 && class(text, next) == start;
+				cur = next;
+				if !same || start == Class::Space {
+					break;
+				}
+			}
+		}
+	}
+	while class(text, cur) == Class::Space {
+		match forward(text, cur) {
+			None => return cur,
+			Some(next) => cur = next,
+		}
+	}
+	cur
+}
+
+fn word_back(text: &[Vec<char>], pos: Position) -> Position {
+	let mut cur = match backward(text, pos) {
+		None => return pos,
+		Some(prev) => prev,
+	};
+	while class(text, cur) == Class::Space {
+		match backward(text, cur) {
+			None => return cur,
+			Some(prev) => cur = prev,
+		}
+	}
+	let word = class(text, cur);
+	while let Some(prev) = backward(text, cur) {
+		if word == Class::Empty || prev.0 != cur.0 || class(text, prev) != word {
+			break;
+		}
+		cur = prev;
+	}
+	cur
+}
+
+fn word_end(text: &[Vec<char>], pos: Position) -> Position {
+	let mut cur = match forward(text, pos) {
+		None => return pos,
+		Some(next) => next,
+	};
+	while matches!(class(text, cur), Class::Space | Class::Empty) {
+		match forward(text, cur) {
+			None => return cur,
+			Some(next) => cur = next,
+		}
+	}
+	let word = class(text, cur);
+	while let Some(next) = forward(text, cur) {
+		if next.0 != cur.0 || class(text, next) != word {
+			break;
+		}
+		cur = next;
+	}
+	cur
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn text(s: &str) -> Vec<Vec<char>> {
+		s.split('\n').map(|l| l.chars().collect()).collect()
+	}
+
+	#[test]
+	fn parse() {
+		assert_eq!(Motion::parse(&['w']), Some(Motion::WordStart));
+		assert_eq!(Motion::parse(&['f', '(']), Some(Motion::Find('(')));
+		assert_eq!(Motion::parse(&['g', 'g']), Some(Motion::Top));
+		assert_eq!(Motion::parse(&['g']), None);
+		assert!(Motion::is_prefix(&['t']));
+		assert!(!Motion::is_prefix(&['x']));
+	}
+
+	#[test]
+	fn words() {
+		let text = text("fn main() {\n\n    let x = 1;");
+		assert_eq!(Motion::WordStart.apply(&text, (0, 0)), (0, 3));
+		assert_eq!(Motion::WordStart.apply(&text, (0, 3)), (0, 7));
+		assert_eq!(Motion::WordStart.apply(&text, (0, 10)), (1, 0));
+		assert_eq!(Motion::WordStart.apply(&text, (1, 0)), (2, 4));
+		assert_eq!(Motion::WordEnd.apply(&text, (0, 0)), (0, 1));
+		assert_eq!(Motion::WordEnd.apply(&text, (0, 10)), (2, 6));
+		assert_eq!(Motion::WordBack.apply(&text, (2, 4)), (1, 0));
+		assert_eq!(Motion::WordBack.apply(&text, (0, 7)), (0, 3));
+	}
+
+	#[test]
+	fn lines() {
+		let text = text("abcdef\nab\nabcd");
+		assert_eq!(Motion::Down.apply(&text, (0, 5)), (1, 1));
+		assert_eq!(Motion::Up.apply(&text, (0, 5)), (0, 5));
+		assert_eq!(Motion::Right.apply(&text, (1, 1)), (1, 1));
+		assert_eq!(Motion::Find('e').apply(&text, (0, 0)), (0, 4));
+		assert_eq!(Motion::Till('e').apply(&text, (0, 0)), (0, 3));
+		assert_eq!(Motion::Bottom.apply(&text, (0, 3)), (2, 0));
+		assert_eq!(Motion::Top.apply(&text, (2, 3)), (0, 0));
+	}
+
+	#[test]
+	fn optimal_keys() {
+		let text = text("let value = compute(a, b);\nreturn value;");
+		assert_eq!(optimal(&text, (0, 0), (0, 0)), Some(0));
+		assert_eq!(optimal(&text, (0, 0), (0, 4)), Some(1));
+		assert_eq!(optimal(&text, (0, 0), (0, 19)), Some(2));
+		assert_eq!(optimal(&text, (0, 0), (1, 0)), Some(1));
+	}
+}
@@ -0,0 +1,320 @@
+use crate::types::motion::{optimal, Motion, Position};
+use anyhow::{anyhow, Result};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::time::{Duration, Instant};
+
+/// Vim motion practice: move the cursor onto each highlighted target, scored
+/// against the fewest keys that would have reached it.
+#[derive(Clone, Debug)]
+pub enum Navigation {
+	BeforeStart(State),
+	Running(State),
+	Finish(State),
+}
+
+#[derive(Clone, Debug)]
+pub struct State {
+	text: Vec<Vec<char>>,
+	cursor: Position,
+	target: Position,
+	pending: Vec<char>,
+	/// Keys pressed and the optimal count for the targets reached so far.
+	keys: usize,
+	optimal: usize,
+	reached: usize,
+	target_keys: usize,
+	target_optimal: usize,
+	remaining_time: Duration,
+	start_time: Option<Instant>,
+	end_time: Option<Instant>,
+	rng: StdRng,
+}
+
+impl Navigation {
+	pub fn new(text: &str, remaining_time: Duration, display_lines: usize) -> Result<Self> {
+		let text: Vec<Vec<char>> = text
+			.split('\n')
+			.take(display_lines)
+			.map(|l| l.chars().collect())
+			.collect();
+		if text.iter().flatten().filter(|c| !c.is_whitespace()).count() < 2 {
+			return Err(anyhow!("text has too few chars to move between"));
+		}
+
+		let state = State {
+			text,
+			cursor: (0, 0),
+			target: (0, 0),
+			pending: Vec::new(),
+			keys: 0,
+			optimal: 0,
+			reached: 0,
+			target_keys: 0,
+			target_optimal: 0,
+			remaining_time,
+			start_time: None,
+			end_time: None,
+			rng: StdRng::from_entropy(),
+		};
+		Ok(Navigation::BeforeStart(state.next_target()))
+	}
+
+	pub fn restart(&self, remaining_time: Duration) -> Self {
+		match self.clone() {
+			Navigation::Finish(s) => Navigation::BeforeStart(
+				State {
+					cursor: (0, 0),
+					pending: Vec::new(),
+					keys: 0,
+					optimal: 0,
+					reached: 0,
+					remaining_time,
+					start_time: None,
+					end_time: None,
+					..s
+				}
+				.next_target(),
+			),
+			other => other,
+		}
+	}
+
+	pub fn start(&self) -> Self {
+		match self.clone() {
+			Navigation::BeforeStart(s) => Navigation::Running(State {
+				start_time: Some(Instant::now()),
+				..s
+			}),
+			other => other,
+		}
+	}
+
+	pub fn finish(&self) -> Self {
+		match self.clone() {
+			Navigation::Running(s) => Navigation::Finish(State {
+				end_time: Some(Instant::now()),
+				..s
+			}),
+			other => other,
+		}
+	}
+
+	pub fn input(&self, c: char) -> Self {
+		match self.clone() {
+			Navigation::Running(s) => Navigation::Running(s.input(c)),
+			other => other,
+		}
+	}
+
+	/// Drops a half-typed motion such as `f` waiting for its char.
+	pub fn clear(&self) -> Self {
+		match self.clone() {
+			Navigation::Running(s) => Navigation::Running(State {
+				pending: Vec::new(),
+				..s
+			}),
+			other => other,
+		}
+	}
+
+	pub fn tick(&self) -> Self {
+		match self.clone() {
+			Navigation::Running(s) if s.remaining_time <= Duration::from_secs(1) => {
+				Navigation::Running(State {
+					remaining_time: Duration::from_secs(0),
+					..s
+				})
+				.finish()
+			}
+			Navigation::Running(s) => Navigation::Running(State {
+				remaining_time: s.remaining_time - Duration::from_secs(1),
+				..s
+			}),
+			other => other,
+		}
+	}
+
+	pub fn state(&self) -> &State {
+		match self {
+			Navigation::BeforeStart(s) => s,
+			Navigation::Running(s) => s,
+			Navigation::Finish(s) => s,
+		}
+	}
+
+	pub fn is_before_start(&self) -> bool {
+		matches!(self, Navigation::BeforeStart(_))
+	}
+
+	pub fn is_finish(&self) -> bool {
+		matches!(self, Navigation::Finish(_))
+	}
+}
+
+impl State {
+	fn input(self, c: char) -> Self {
+		let mut pending = self.pending.clone();
+		pending.push(c);
+		let state = State {
+			target_keys: self.target_keys + 1,
+			pending: Vec::new(),
+			..self
+		};
+
+		match Motion::parse(&pending) {
+			Some(motion) => {
+				let cursor = motion.apply(&state.text, state.cursor);
+				if cursor == state.target {
+					State {
+						cursor,
+						keys: state.keys + state.target_keys,
+						optimal: state.optimal + state.target_optimal,
+						reached: state.reached + 1,
+						..state
+					}
+					.next_target()
+				} else {
+					State { cursor, ..state }
+				}
+			}
+			None if Motion::is_prefix(&pending) => State { pending, ..state },
+			None => state,
+		}
+	}
+
+	/// Picks a new non-blank target away from the cursor and resets its counts.
+	fn next_target(mut self) -> Self {
+		let candidates: Vec<Position> = self
+			.text
+			.iter()
+			.enumerate()
+			.flat_map(|(r, line)| {
+				line.iter()
+					.enumerate()
+					.filter(|(_, c)| !c.is_whitespace())
+					.map(move |(i, _)| (r, i))
+			})
+			.filter(|p| *p != self.cursor)
+			.collect();
+		let target = *candidates.choose(&mut self.rng).unwrap_or(&self.cursor);
+		State {
+			target,
+			target_keys: 0,
+			target_optimal: optimal(&self.text, self.cursor, target).unwrap_or(0),
+			..self
+		}
+	}
+
+	pub fn text(&self) -> &[Vec<char>] {
+		&self.text
+	}
+
+	pub fn cursor(&self) -> Position {
+		self.cursor
+	}
+
+	pub fn target(&self) -> Position {
+		self.target
+	}
+
+	pub fn pending(&self) -> String {
+		self.pending.iter().collect()
+	}
+
+	pub fn reached(&self) -> usize {
+		self.reached
+	}
+
+	pub fn keys(&self) -> usize {
+		self.keys
+	}
+
+	pub fn optimal(&self) -> usize {
+		self.optimal
+	}
+
+	/// Optimal keys as a percentage of the keys pressed for the targets reached.
+	pub fn efficiency(&self) -> usize {
+		if self.keys == 0 {
+			0
+		} else {
+			(self.optimal as f64 / self.keys as f64 * 100.0).round() as usize
+		}
+	}
+
+	pub fn remaining_time(&self) -> Duration {
+		self.remaining_time
+	}
+
+	pub fn running_time(&self) -> Duration {
+		self.end_time
+			.unwrap_or(Instant::now())
+			.duration_since(self.start_time.unwrap_or(Instant::now()))
+	}
+
+	/// Average seconds per reached target.
+	pub fn pace(&self) -> f64 {
+		if self.reached == 0 {
+			0.0
+		} else {
+			self.running_time().as_secs_f64() / self.reached as f64
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reach_target() {
+		let navigation = Navigation::new("ab cd\nef gh", Duration::from_secs(10), 10)
+			.unwrap()
+			.start();
+		let state = State {
+			target: (1, 3),
+			target_optimal: 2,
+			..navigation.state().clone()
+		};
+		let navigation = Navigation::Running(state).input('x').input('j');
+		assert_eq!(navigation.state().cursor(), (1, 0));
+		assert_eq!(navigation.state().reached(), 0);
+
+		let state = navigation.input('w').state().clone();
+		assert_eq!(state.reached(), 1);
+		assert_eq!((state.keys(), state.optimal()), (3, 2));
+		assert_eq!(state.efficiency(), 67);
+		assert_ne!(state.target(), state.cursor());
+	}
+
+	#[test]
+	fn pending_motion() {
+		let navigation = Navigation::new("ab cd", Duration::from_secs(10), 10)
+			.unwrap()
+			.start()
+			.input('f');
+		assert_eq!(navigation.state().pending(), "f");
+		assert_eq!(navigation.clear().state().pending(), "");
+
+		let navigation = navigation.input('x').input('q');
+		assert_eq!(navigation.state().pending(), "");
+		assert_eq!(navigation.state().cursor(), (0, 0));
+	}
+
+	#[test]
+	fn too_short() {
+		assert!(Navigation::new(" a ", Duration::from_secs(10), 10).is_err());
+	}
+
+	#[test]
+	fn tick() {
+		let navigation = Navigation::new("ab cd", Duration::from_secs(2), 10)
+			.unwrap()
+			.start()
+			.tick();
+		assert!(!navigation.is_finish());
+		assert!(navigation.tick().is_finish());
+	}
+}
@@ -0,0 +1,50 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ErrorPolicy {
+	#[default]
+	StopOnLetter,
+	AdvanceAnyway,
+	StopOnWord,
+	SuddenDeath,
+}
+
+impl ErrorPolicy {
+	pub fn parse(name: &str) -> Result<Self> {
+		match name {
+			"letter" => Ok(ErrorPolicy::StopOnLetter),
+			"advance" => Ok(ErrorPolicy::AdvanceAnyway),
+			"word" => Ok(ErrorPolicy::StopOnWord),
+			"sudden-death" => Ok(ErrorPolicy::SuddenDeath),
+			_ => Err(anyhow!(
+				"Unknown error policy {}, expected letter, advance, word or sudden-death.",
+				name
+			)),
+		}
+	}
+
+	pub fn label(&self) -> &'static str {
+		match self {
+			ErrorPolicy::StopOnLetter => "stop on letter",
+			ErrorPolicy::AdvanceAnyway => "advance anyway",
+			ErrorPolicy::StopOnWord => "stop on word",
+			ErrorPolicy::SuddenDeath => "sudden death",
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse() {
+		assert_eq!(ErrorPolicy::parse("word").unwrap(), ErrorPolicy::StopOnWord);
+		assert_eq!(
+			ErrorPolicy::parse("sudden-death").unwrap(),
+			ErrorPolicy::SuddenDeath
+		);
+		assert!(ErrorPolicy::parse("never").is_err());
+	}
+}
@@ -0,0 +1,37 @@
+use anyhow::{anyhow, Result};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum StartMode {
+	/// The timer starts with the first typed char.
+	#[default]
+	FirstKey,
+	Enter,
+	/// Enter starts a 3-2-1 countdown, then the timer.
+	Countdown,
+}
+
+impl StartMode {
+	pub fn parse(name: &str) -> Result<Self> {
+		match name {
+			"key" => Ok(StartMode::FirstKey),
+			"enter" => Ok(StartMode::Enter),
+			"countdown" => Ok(StartMode::Countdown),
+			_ => Err(anyhow!(
+				"Unknown start mode {}, expected key, enter or countdown.",
+				name
+			)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse() {
+		assert_eq!(StartMode::parse("enter").unwrap(), StartMode::Enter);
+		assert_eq!(StartMode::parse("countdown").unwrap(), StartMode::Countdown);
+		assert!(StartMode::parse("now").is_err());
+	}
+}
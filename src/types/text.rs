@@ -0,0 +1,54 @@
+use std::ops::Range;
+
+/// The whole text as one buffer of chars, with the range each line covers.
+/// Typing states share it, so a keystroke never copies the text.
+#[derive(Debug, PartialEq)]
+pub struct Text {
+	chars: Vec<char>,
+	lines: Vec<Range<usize>>,
+}
+
+impl Text {
+	pub fn new(text: &str) -> Self {
+		let chars: Vec<char> = text.chars().collect();
+		let mut lines = Vec::new();
+		let mut start = 0;
+		for (i, c) in chars.iter().enumerate() {
+			if *c == '\n' {
+				lines.push(start..i);
+				start = i + 1;
+			}
+		}
+		lines.push(start..chars.len());
+		Text { chars, lines }
+	}
+
+	pub fn line_count(&self) -> usize {
+		self.lines.len()
+	}
+
+	/// Where line `index` starts in the buffer.
+	pub fn offset(&self, index: usize) -> usize {
+		self.lines[index].start
+	}
+
+	pub fn line(&self, index: usize) -> &[char] {
+		&self.chars[self.lines[index].clone()]
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn lines() {
+		let text = Text::new("ab\n\n  cd");
+		assert_eq!(text.line_count(), 3);
+		assert_eq!(text.line(0), ['a', 'b']);
+		assert!(text.line(1).is_empty());
+		assert_eq!(text.line(2), [' ', ' ', 'c', 'd']);
+		assert_eq!(text.offset(2), 4);
+		assert_eq!(Text::new("ab\n").line_count(), 2);
+	}
+}
@@ -1,6 +1,10 @@
+use crate::types::keystroke::Keystroke;
 use crate::types::line::Line;
+use crate::types::policy::ErrorPolicy;
+use crate::types::text::Text;
 use anyhow::{anyhow, Result};
 use std::cmp;
+use std::rc::Rc;
 use std::time::{Duration, Instant};
 
 #[derive(Clone, Debug)]
@@ -13,14 +17,24 @@ pub enum Typing {
 #[derive(Clone, Debug)]
 pub struct State {
 	current_index: usize,
+	/// Index within the current line of the char to type next.
+	cursor: usize,
 	display_lines: usize,
+	editor: bool,
 	end_time: Option<std::time::Instant>,
+	/// Mistyped positions in the text, in the order they were typed.
+	errors: Vec<usize>,
+	/// Opener and closer positions on the current line of pairs the editor closes on its own.
+	closers: Vec<(usize, usize)>,
 	is_error: bool,
-	lines: Vec<Line>,
+	keystrokes: Vec<Keystroke>,
+	text: Rc<Text>,
+	policy: ErrorPolicy,
 	remaining_time: Duration,
 	start_time: Option<std::time::Instant>,
 	typed: usize,
 	typo: usize,
+	warm_up: bool,
 }
 
 impl Typing {
@@ -28,126 +42,230 @@ impl Typing {
 		if text.is_empty() {
 			Err(anyhow!("text is empty"))
 		} else {
-			let lines: Vec<Line> = text
-				.split("\n")
-				.enumerate()
-				.map(|(i, v)| Line::new(i + 1, &v.to_string()))
-				.collect();
+			let text = Text::new(text);
 			Ok(Typing::BeforeStart(State {
 				current_index: 0,
-				lines: lines.clone(),
+				cursor: Line::indent(text.line(0)),
+				text: Rc::new(text),
 				start_time: None,
 				end_time: None,
-				remaining_time: remaining_time,
+				remaining_time,
 				typed: 0,
 				typo: 0,
+				errors: Vec::new(),
+				closers: Vec::new(),
 				is_error: false,
-				display_lines: display_lines,
+				display_lines,
+				policy: ErrorPolicy::default(),
+				keystrokes: Vec::new(),
+				warm_up: false,
+				editor: false,
 			}))
 		}
 	}
 
-	pub fn restart(&self, text: &str, remaining_time: Duration) -> Self {
+	pub fn restart(self, text: &str, remaining_time: Duration) -> Self {
 		match self {
-			Typing::Finish(s) => Typing::BeforeStart(State {
-				current_index: 0,
-				lines: Typing::to_lines(text),
-				start_time: None,
-				end_time: None,
-				remaining_time: remaining_time,
-				typed: 0,
-				typo: 0,
-				..s.clone()
-			}),
-			Typing::Running(s) => Typing::Running(s.clone()),
-			Typing::BeforeStart(s) => Typing::BeforeStart(s.clone()),
+			Typing::Finish(s) => {
+				let text = Text::new(text);
+				Typing::BeforeStart(State {
+					current_index: 0,
+					cursor: Line::indent(text.line(0)),
+					text: Rc::new(text),
+					start_time: None,
+					end_time: None,
+					remaining_time,
+					typed: 0,
+					typo: 0,
+					errors: Vec::new(),
+					closers: Vec::new(),
+					is_error: false,
+					keystrokes: Vec::new(),
+					..s
+				})
+			}
+			other => other,
 		}
 	}
 
-	pub fn start(&self) -> Self {
+	pub fn start(self) -> Self {
 		match self {
 			Typing::BeforeStart(s) => Typing::Running(State {
 				start_time: Some(Instant::now()),
-				..s.clone()
+				..s
 			}),
-			Typing::Running(t) => Typing::Running(t.clone()),
-			Typing::Finish(t) => Typing::Finish(t.clone()),
+			other => other,
 		}
 	}
 
-	pub fn finish(&self) -> Self {
-		match self.clone() {
+	pub fn finish(self) -> Self {
+		match self {
 			Typing::Running(t) => Typing::Finish(State {
 				end_time: Some(Instant::now()),
-				..t.clone()
+				..t
 			}),
-			Typing::BeforeStart(lines) => Typing::BeforeStart(lines),
-			Typing::Finish(t) => Typing::Finish(t),
+			other => other,
 		}
 	}
 
-	pub fn input(&self, c: char) -> Self {
+	pub fn input(self, c: char) -> Self {
 		match self {
-			Typing::Running(t) => {
-				let current_line = t.current();
-				let entered = current_line.input(c);
-				let mut lines = t.lines.clone();
+			Typing::Running(mut t) => {
+				let entered = t.current().input(c);
+				t.record(c, entered);
 
 				if entered {
-					let next = current_line.next();
+					if t.editor {
+						t.advance_auto_close();
+					} else {
+						t.advance();
+					}
 
-					if next.is_entered() {
-						self.next()
+					if t.current().is_entered() {
+						Typing::Running(t).next()
 					} else {
-						lines[t.current_index] = next;
-						Typing::Running(State {
-							lines: lines,
-							typed: t.typed + 1,
-							is_error: false,
-							..t.clone()
-						})
+						t.typed += 1;
+						t.is_error = false;
+						Typing::Running(t)
 					}
 				} else {
-					Typing::Running(State {
-						lines: lines,
-						typed: t.typed,
-						typo: t.typo + 1,
-						is_error: true,
-						..t.clone()
-					})
+					t.typo += 1;
+					t.is_error = true;
+					match t.policy {
+						// The warm-up isn't scored, so a typo only has to be retyped.
+						_ if t.is_warming_up() => Typing::Running(t),
+						ErrorPolicy::StopOnLetter => Typing::Running(t),
+						ErrorPolicy::AdvanceAnyway => {
+							t.miss();
+							if t.editor {
+								t.skip_closers();
+							}
+							if t.current().is_entered() {
+								Typing::Running(t).next()
+							} else {
+								Typing::Running(t)
+							}
+						}
+						ErrorPolicy::StopOnWord => {
+							t.rewind_word();
+							Typing::Running(t)
+						}
+						ErrorPolicy::SuddenDeath => Typing::Running(t).finish(),
+					}
 				}
 			}
-			Typing::BeforeStart(t) => Typing::BeforeStart(t.clone()),
-			Typing::Finish(t) => Typing::Finish(t.clone()),
+			other => other,
 		}
 	}
 
-	pub fn next(&self) -> Self {
-		match self.clone() {
-			Typing::Running(t) => {
-				if t.current_index + 1 < t.lines.len() {
+	pub fn next(self) -> Self {
+		match self {
+			Typing::Running(mut t) => {
+				if t.is_warming_up() {
+					// The scored test starts on the line after the warm-up.
+					t.next_line();
 					Typing::Running(State {
-						current_index: t.current_index + 1,
-						lines: t.lines.clone(),
-						..t.clone()
+						start_time: Some(Instant::now()),
+						typed: 0,
+						typo: 0,
+						keystrokes: Vec::new(),
+						..t
 					})
+				} else if t.current_index + 1 < t.text.line_count() {
+					t.next_line();
+					Typing::Running(t)
 				} else {
-					self.finish()
+					Typing::Running(t).finish()
 				}
 			}
-			Typing::Finish(t) => Typing::Finish(t.clone()),
-			Typing::BeforeStart(t) => Typing::BeforeStart(t.clone()),
+			other => other,
+		}
+	}
+
+	/// Makes the first line a warm-up: it is typed before the timer starts and isn't scored.
+	pub fn with_warm_up(self, warm_up: bool) -> Self {
+		match self {
+			Typing::Running(t) => Typing::Running(State { warm_up, ..t }),
+			Typing::Finish(t) => Typing::Finish(State { warm_up, ..t }),
+			Typing::BeforeStart(t) => Typing::BeforeStart(State { warm_up, ..t }),
 		}
 	}
 
-	pub fn display_lines(&self) -> Vec<Line> {
-		match self.clone() {
-			Typing::Running(t) => t.display_lines(),
-			Typing::Finish(t) => t.display_lines(),
-			Typing::BeforeStart(t) => t.display_lines(),
+	/// Whether the first line was set aside as a warm-up, whether or not it's done.
+	pub fn has_warm_up(&self) -> bool {
+		self.state().warm_up
+	}
+
+	pub fn is_warming_up(&self) -> bool {
+		match self {
+			Typing::Running(s) => s.is_warming_up(),
+			_ => false,
 		}
 	}
 
+	/// Skips closing brackets and quotes the way an editor inserts them.
+	pub fn with_editor(self, editor: bool) -> Self {
+		match self {
+			Typing::Running(t) => Typing::Running(State { editor, ..t }),
+			Typing::Finish(t) => Typing::Finish(State { editor, ..t }),
+			Typing::BeforeStart(t) => Typing::BeforeStart(State { editor, ..t }),
+		}
+	}
+
+	pub fn is_editor(&self) -> bool {
+		self.state().editor
+	}
+
+	pub fn with_policy(self, policy: ErrorPolicy) -> Self {
+		match self {
+			Typing::Running(t) => Typing::Running(State { policy, ..t }),
+			Typing::Finish(t) => Typing::Finish(State { policy, ..t }),
+			Typing::BeforeStart(t) => Typing::BeforeStart(State { policy, ..t }),
+		}
+	}
+
+	pub fn keystrokes(&self) -> &[Keystroke] {
+		match self {
+			Typing::Running(s) => &s.keystrokes,
+			Typing::Finish(s) => &s.keystrokes,
+			Typing::BeforeStart(_) => &[],
+		}
+	}
+
+	pub fn running_time(&self) -> Duration {
+		match self {
+			Typing::Running(s) => s.running_time(),
+			Typing::Finish(s) => s.running_time(),
+			Typing::BeforeStart(_) => Duration::from_secs(0),
+		}
+	}
+
+	/// Moves the start time so that `elapsed` has passed, used to drive a replay on its own clock.
+	pub fn with_elapsed(self, elapsed: Duration) -> Self {
+		match self {
+			Typing::Running(t) => Typing::Running(State {
+				start_time: Instant::now().checked_sub(elapsed).or(t.start_time),
+				..t
+			}),
+			other => other,
+		}
+	}
+
+	pub fn lines_text(&self) -> Vec<String> {
+		let text = &self.state().text;
+		(0..text.line_count())
+			.map(|i| text.line(i).iter().collect())
+			.collect()
+	}
+
+	pub fn policy(&self) -> ErrorPolicy {
+		self.state().policy
+	}
+
+	pub fn display_lines(&self) -> Vec<Line<'_>> {
+		self.state().display_lines()
+	}
+
 	pub fn is_finish(&self) -> bool {
 		match self {
 			Typing::Finish(_) => true,
@@ -194,51 +312,47 @@ impl Typing {
 		}
 	}
 
-	pub fn tick(&self) -> Self {
+	pub fn tick(self) -> Self {
 		match self {
+			Typing::Running(t) if t.is_warming_up() => Typing::Running(t),
 			Typing::Running(t) => {
 				if t.remaining_time == Duration::from_secs(0) {
-					self.finish()
+					Typing::Running(t).finish()
 				} else {
 					Typing::Running(State {
-						remaining_time: if t.remaining_time == Duration::from_secs(0)
-							|| t.remaining_time - Duration::from_secs(1) <= Duration::from_secs(1)
+						remaining_time: if t.remaining_time - Duration::from_secs(1)
+							<= Duration::from_secs(1)
 						{
 							Duration::from_secs(0)
 						} else {
 							t.remaining_time - Duration::from_secs(1)
 						},
-						..t.clone()
+						..t
 					})
 				}
 			}
-			Typing::Finish(t) => Typing::Finish(t.clone()),
-			Typing::BeforeStart(t) => Typing::BeforeStart(t.clone()),
+			other => other,
 		}
 	}
 
 	pub fn get_remaining_time(&self) -> usize {
-		match self.clone() {
-			Typing::Running(t) => t.remaining_time.as_secs() as usize,
-			Typing::Finish(t) => t.remaining_time.as_secs() as usize,
-			Typing::BeforeStart(t) => t.remaining_time.as_secs() as usize,
-		}
+		self.state().remaining_time.as_secs() as usize
 	}
 
-	pub fn update_remaining_time(&self, time: Duration) -> Self {
-		match self.clone() {
-			Typing::Running(mut t) => {
-				t.remaining_time = time;
-				Typing::Running(t)
-			}
-			Typing::Finish(mut t) => {
-				t.remaining_time = time;
-				Typing::Finish(t)
-			}
-			Typing::BeforeStart(mut t) => {
-				t.remaining_time = time;
-				Typing::BeforeStart(t)
-			}
+	pub fn update_remaining_time(self, time: Duration) -> Self {
+		match self {
+			Typing::Running(t) => Typing::Running(State {
+				remaining_time: time,
+				..t
+			}),
+			Typing::Finish(t) => Typing::Finish(State {
+				remaining_time: time,
+				..t
+			}),
+			Typing::BeforeStart(t) => Typing::BeforeStart(State {
+				remaining_time: time,
+				..t
+			}),
 		}
 	}
 
@@ -258,11 +372,21 @@ impl Typing {
 		}
 	}
 
-	fn to_lines(text: &str) -> Vec<Line> {
-		text.split("\n")
-			.enumerate()
-			.map(|(i, v)| Line::new(i + 1, &v.to_string()))
-			.collect()
+	/// The char expected next, if the test isn't finished.
+	pub fn current_text(&self) -> Option<char> {
+		match self {
+			Typing::Running(s) => s.current().current_text(),
+			Typing::BeforeStart(s) => s.current().current_text(),
+			Typing::Finish(_) => None,
+		}
+	}
+
+	fn state(&self) -> &State {
+		match self {
+			Typing::Running(s) => s,
+			Typing::Finish(s) => s,
+			Typing::BeforeStart(s) => s,
+		}
 	}
 }
 
@@ -273,22 +397,103 @@ impl State {
 			.duration_since(self.start_time.unwrap_or(Instant::now()))
 	}
 
-	pub fn display_lines(&self) -> Vec<Line> {
-		if self.lines.len() <= self.display_lines {
-			self.lines.clone()
+	pub fn display_lines(&self) -> Vec<Line<'_>> {
+		let line_count = self.text.line_count();
+		let start_index = if line_count <= self.display_lines {
+			0
 		} else {
-			let start_index = if self.current_index > 0 {
-				self.current_index - 1
-			} else {
-				0
-			};
-			self.lines[start_index..cmp::min(self.lines.len(), start_index + self.display_lines)]
-				.to_vec()
+			self.current_index.saturating_sub(1)
+		};
+		(start_index..cmp::min(line_count, start_index + self.display_lines))
+			.map(|i| self.line(i))
+			.collect()
+	}
+
+	fn is_warming_up(&self) -> bool {
+		self.warm_up && self.current_index == 0 && self.text.line_count() > 1
+	}
+
+	fn record(&mut self, key: char, correct: bool) {
+		let line = self.current();
+		let keystroke = Keystroke {
+			at: self.running_time().as_millis() as u64,
+			key,
+			expected: line.current_text(),
+			correct,
+			line_no: line.line_no(),
+			column: line.cursor(),
+		};
+		self.keystrokes.push(keystroke);
+	}
+
+	pub fn current(&self) -> Line<'_> {
+		self.line(self.current_index)
+	}
+
+	/// Line `index` as typed so far: entered before the current line, untouched after it.
+	fn line(&self, index: usize) -> Line<'_> {
+		let chars = self.text.line(index);
+		let offset = self.text.offset(index);
+		let from = self.errors.partition_point(|i| *i < offset);
+		let to = self.errors.partition_point(|i| *i < offset + chars.len());
+		let line = Line::new(index + 1, chars).with_errors(offset, &self.errors[from..to]);
+		match index.cmp(&self.current_index) {
+			cmp::Ordering::Less => line.with_cursor(chars.len()),
+			cmp::Ordering::Equal => line.with_cursor(self.cursor),
+			cmp::Ordering::Greater => line,
+		}
+	}
+
+	fn advance(&mut self) {
+		if !self.current().is_entered() {
+			self.cursor += 1;
 		}
 	}
 
-	pub fn current(&self) -> Line {
-		self.lines.get(self.current_index).unwrap().clone()
+	/// Moves past the current char like `advance`, but remembers it as mistyped.
+	fn miss(&mut self) {
+		if !self.current().is_entered() {
+			self.errors
+				.push(self.text.offset(self.current_index) + self.cursor);
+		}
+		self.advance();
+	}
+
+	/// Moves past the current char like `advance`, the way an editor with auto-closing
+	/// pairs would: typing an opener closes it, and reaching that closer skips it.
+	fn advance_auto_close(&mut self) {
+		let opener = self.cursor;
+		if let Some(close) = self.current().matching_closer() {
+			self.closers.push((opener, close));
+		}
+		self.advance();
+		self.skip_closers();
+	}
+
+	/// Moves past closers the editor already inserted.
+	fn skip_closers(&mut self) {
+		while !self.current().is_entered() && self.closers.iter().any(|(_, c)| *c == self.cursor) {
+			self.advance();
+		}
+	}
+
+	/// Moves the cursor back to the start of the word being typed.
+	fn rewind_word(&mut self) {
+		let start = self.current().word_start();
+		if start == self.cursor {
+			return;
+		}
+		let offset = self.text.offset(self.current_index);
+		self.cursor = start;
+		self.errors
+			.truncate(self.errors.partition_point(|i| *i < offset + start));
+		self.closers.retain(|(opener, _)| *opener < start);
+	}
+
+	fn next_line(&mut self) {
+		self.current_index += 1;
+		self.cursor = Line::indent(self.text.line(self.current_index));
+		self.closers.clear();
 	}
 
 	pub fn wpm(&self) -> usize {
@@ -370,4 +575,212 @@ mod tests {
 
 		assert_eq!(typing.acc(), 80);
 	}
+
+	fn start_with(policy: ErrorPolicy) -> Typing {
+		Typing::new("ab cd\nef", Duration::from_secs(10), 10)
+			.unwrap()
+			.with_policy(policy)
+			.start()
+	}
+
+	#[test]
+	fn stop_on_letter() {
+		let typing = start_with(ErrorPolicy::StopOnLetter).input('x');
+		assert!(typing.is_error());
+		assert_eq!(typing.display_lines()[0].current_text(), Some('a'));
+		assert_eq!(typing.typo(), 1);
+	}
+
+	#[test]
+	fn advance_anyway() {
+		let typing = start_with(ErrorPolicy::AdvanceAnyway).input('x');
+		assert_eq!(typing.display_lines()[0].current_text(), Some('b'));
+		assert_eq!(typing.typo(), 1);
+
+		let typing = "b cx".chars().fold(typing, |t, c| t.input(c));
+		assert_eq!(typing.current_line_index(), 1);
+		assert_eq!(typing.typo(), 2);
+	}
+
+	#[test]
+	fn stop_on_word() {
+		let typing = "ab cx"
+			.chars()
+			.fold(start_with(ErrorPolicy::StopOnWord), |t, c| t.input(c));
+		assert_eq!(typing.display_lines()[0].current_text(), Some('c'));
+		assert_eq!(typing.typo(), 1);
+	}
+
+	#[test]
+	fn sudden_death() {
+		let typing = start_with(ErrorPolicy::SuddenDeath).input('a').input('x');
+		assert!(typing.is_finish());
+		assert_eq!(typing.policy(), ErrorPolicy::SuddenDeath);
+
+		let typing = Typing::new("ab\ncd", Duration::from_secs(10), 10)
+			.unwrap()
+			.with_policy(ErrorPolicy::SuddenDeath)
+			.with_warm_up(true)
+			.start()
+			.input('x');
+		assert!(typing.is_warming_up());
+		assert!(!typing.is_finish());
+		assert_eq!(typing.display_lines()[0].current_text(), Some('a'));
+	}
+
+	#[test]
+	fn keystrokes() {
+		let typing = start_with(ErrorPolicy::StopOnLetter)
+			.input('a')
+			.input('x')
+			.input('b');
+		let keystrokes = typing.keystrokes();
+		assert_eq!(keystrokes.len(), 3);
+		assert_eq!(keystrokes[1].key, 'x');
+		assert_eq!(keystrokes[1].expected, Some('b'));
+		assert!(!keystrokes[1].correct);
+		assert!(keystrokes[2].correct);
+		assert!(typing
+			.finish()
+			.restart("ab", Duration::from_secs(10))
+			.keystrokes()
+			.is_empty());
+	}
+
+	#[test]
+	fn warm_up() {
+		let typing = Typing::new("ab\ncd", Duration::from_secs(10), 10)
+			.unwrap()
+			.with_warm_up(true)
+			.start();
+		assert!(typing.is_warming_up());
+
+		let typing = typing.input('a').input('x').tick();
+		assert_eq!(typing.typo(), 1);
+		assert_eq!(typing.get_remaining_time(), 10);
+
+		let typing = typing.input('b');
+		assert!(!typing.is_warming_up());
+		assert_eq!(typing.current_line_index(), 1);
+		assert_eq!((typing.typed(), typing.typo()), (0, 0));
+		assert!(typing.keystrokes().is_empty());
+		assert_eq!(typing.tick().get_remaining_time(), 9);
+
+		let single = Typing::new("ab", Duration::from_secs(10), 10)
+			.unwrap()
+			.with_warm_up(true)
+			.start();
+		assert!(!single.is_warming_up());
+	}
+
+	#[test]
+	fn editor() {
+		let typing = Typing::new("f(x)\ny", Duration::from_secs(10), 10)
+			.unwrap()
+			.with_editor(true)
+			.start()
+			.input('f')
+			.input('(')
+			.input('x');
+		assert_eq!(typing.current_line_index(), 1);
+		assert_eq!(typing.typed(), 2);
+
+		let typing = Typing::new("f(x)", Duration::from_secs(10), 10)
+			.unwrap()
+			.start()
+			.input('f')
+			.input('(')
+			.input('x');
+		assert_eq!(typing.current_text(), Some(')'));
+
+		let typing = Typing::new("f(x)\ny", Duration::from_secs(10), 10)
+			.unwrap()
+			.with_editor(true)
+			.with_policy(ErrorPolicy::AdvanceAnyway)
+			.start()
+			.input('f')
+			.input('(')
+			.input('z');
+		assert_eq!(typing.current_line_index(), 1);
+		assert_eq!(typing.typo(), 1);
+	}
+
+	#[test]
+	fn auto_close() {
+		let typing = Typing::new("f(a[0], \"x\") {", Duration::from_secs(10), 10)
+			.unwrap()
+			.with_editor(true)
+			.start();
+		let typing = "f(".chars().fold(typing, |t, c| t.input(c));
+		assert_eq!(typing.current_text(), Some('a'));
+
+		let typing = "a[0".chars().fold(typing, |t, c| t.input(c));
+		assert_eq!(typing.current_text(), Some(','));
+		assert_eq!(typing.display_lines()[0].entered_text().unwrap(), "f(a[0]");
+
+		let typing = ", \"x".chars().fold(typing, |t, c| t.input(c));
+		assert_eq!(typing.current_text(), Some(' '));
+
+		// The brace's closer isn't on this line, so there is nothing to skip.
+		let typing = " {".chars().fold(typing, |t, c| t.input(c));
+		assert!(typing.is_finish());
+	}
+
+	#[test]
+	fn rewind_word_drops_closers() {
+		let typing = Typing::new("ab (c)", Duration::from_secs(10), 10)
+			.unwrap()
+			.with_editor(true)
+			.with_policy(ErrorPolicy::StopOnWord)
+			.start();
+		let typing = "ab (x".chars().fold(typing, |t, c| t.input(c));
+		assert_eq!(typing.current_text(), Some('('));
+
+		let typing = typing.with_editor(false).input('(').input('c');
+		assert_eq!(typing.current_text(), Some(')'));
+	}
+
+	#[test]
+	fn errors_by_line() {
+		let typing = Typing::new(
+			"ab
+cd",
+			Duration::from_secs(10),
+			10,
+		)
+		.unwrap()
+		.with_policy(ErrorPolicy::AdvanceAnyway)
+		.start();
+		let typing = "xbcy".chars().fold(typing, |t, c| t.input(c));
+		let lines = typing.display_lines();
+		assert_eq!(lines[0].error_indices(), vec![0]);
+		assert_eq!(lines[1].error_indices(), vec![1]);
+		assert!(lines[0].is_entered());
+	}
+
+	/// Keystrokes share the text rather than copy it, so a long text types as fast
+	/// as a short one. The bound is loose: a copy per keystroke is orders slower.
+	#[test]
+	fn input_cost_flat() {
+		fn time(lines: usize) -> Duration {
+			let line = "let value = compute(first, second);";
+			let text = vec![format!("    {}", line); lines].join("\n");
+			let mut typing = Typing::new(&text, Duration::from_secs(60), 10)
+				.unwrap()
+				.start();
+			let start = Instant::now();
+			for c in line.chars().cycle().take(2_000) {
+				typing = typing.input(c);
+			}
+			assert!(!typing.is_finish());
+			start.elapsed()
+		}
+		let (short, long) = (time(100), time(100_000));
+		assert!(
+			long < short * 10 + Duration::from_millis(50),
+			"{:?} on 100 lines, {:?} on 100000",
+			short,
+			long
+		);
+	}
 }
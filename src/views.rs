@@ -1,18 +1,100 @@
 use std::path::PathBuf;
+use std::time::Duration;
 use tui::{
 	backend::Backend,
-	layout::{Alignment, Constraint, Direction, Layout},
+	buffer::Buffer,
+	layout::{Alignment, Constraint, Direction, Layout, Rect},
 	style::{Color, Modifier, Style},
 	symbols,
 	text::{Span, Spans},
-	widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+	widgets::{
+		Axis, BarChart, Block, Borders, Chart, Clear, Dataset, GraphType, List, ListItem,
+		ListState, Paragraph, Widget,
+	},
 	Frame,
 };
 
-use crate::app::App;
+use crate::app::{App, TypingResult};
+use crate::config::parse_duration;
+use crate::history::bests::Comparison;
+use crate::history::goals::DailyProgress;
+use crate::history::stats::{Period, Stats};
+use crate::keyboard::layout::{Finger, Layout as KeyboardLayout};
+use crate::keyboard::stats::{KeyStat, KeyStats};
+use crate::picker::Picker;
+use crate::types::analysis::Analysis;
 use crate::types::line::Line;
+use crate::types::navigation::{Navigation, State as NavigationState};
+use crate::types::start::StartMode;
 use crate::types::typing::Typing;
 
+/// Indent of each keyboard row, in chars, for the ANSI stagger.
+const KEY_ROW_OFFSETS: [usize; 4] = [0, 6, 7, 9];
+const HEAT_WARN_RATE: f64 = 0.05;
+const KEY_WIDTH: usize = 4;
+const SHIFT_ROW: usize = 3;
+const SHIFT_LABEL: &str = "shift";
+const KEYBOARD_HEIGHT: u16 = 5;
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 16;
+
+/// What clicking a part of the screen does.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Click {
+	Time(Duration),
+	Restart,
+	Quit,
+	Next,
+}
+
+/// Clickable areas of the last frame drawn.
+pub type Hotspots = Vec<(Rect, Click)>;
+
+pub fn click_at(hotspots: &Hotspots, column: u16, row: u16) -> Option<Click> {
+	hotspots
+		.iter()
+		.find(|(area, _)| {
+			column >= area.left()
+				&& column < area.right()
+				&& row >= area.top()
+				&& row < area.bottom()
+		})
+		.map(|(_, click)| *click)
+}
+
+/// Draws a warning in place of the layout when the terminal is too small for it.
+fn too_small<B: Backend>(f: &mut Frame<B>, theme: &Theme) -> bool {
+	let size = f.size();
+	if size.width >= MIN_WIDTH && size.height >= MIN_HEIGHT {
+		return false;
+	}
+	let text = vec![
+		Spans::from(Span::styled(
+			"terminal too small",
+			Style::default()
+				.bg(theme.bg())
+				.fg(Color::Yellow)
+				.add_modifier(Modifier::BOLD),
+		)),
+		Spans::from(Span::styled(
+			format!(
+				"{}x{}, needs {}x{}",
+				size.width, size.height, MIN_WIDTH, MIN_HEIGHT
+			),
+			Style::default().bg(theme.bg()).fg(Color::DarkGray),
+		)),
+	];
+	let top = size.height.saturating_sub(text.len() as u16) / 2;
+	let area = Rect::new(size.x, size.y + top, size.width, size.height - top);
+	f.render_widget(
+		Paragraph::new(text)
+			.alignment(Alignment::Center)
+			.style(Style::default().bg(theme.bg()).fg(theme.fg())),
+		area,
+	);
+	true
+}
+
 pub enum Theme {
 	Dark,
 	Light,
@@ -42,7 +124,16 @@ impl Theme {
 	}
 }
 
-pub fn view<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, file: PathBuf) {
+pub fn view<B: Backend>(
+	f: &mut Frame<B>,
+	app: &App,
+	theme: &Theme,
+	file: PathBuf,
+	picking: bool,
+) -> Hotspots {
+	if too_small(f, theme) {
+		return Vec::new();
+	}
 	if app.typing.is_finish() {
 		let result = app.result();
 		let chunks = Layout::default()
@@ -50,18 +141,71 @@ pub fn view<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, file: PathBu
 			.constraints(
 				[
 					Constraint::Percentage(10),
-					Constraint::Percentage(70),
+					Constraint::Length(2),
+					Constraint::Percentage(60),
 					Constraint::Percentage(20),
 				]
 				.as_ref(),
 			)
 			.split(f.size());
 		f.render_widget(result_view(&app.typing, Borders::BOTTOM, theme), chunks[0]);
+		f.render_widget(bests_view(app.bests(), theme), chunks[1]);
+		let analysis = app.analysis();
+		let panes = Layout::default()
+			.direction(Direction::Horizontal)
+			.constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+			.split(chunks[2]);
+		let details = Layout::default()
+			.direction(Direction::Vertical)
+			.constraints([Constraint::Min(0), Constraint::Length(8)].as_ref())
+			.split(panes[1]);
+		if app.shows_keyboard() {
+			let stats = app.key_stats();
+			let keyboard = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints([Constraint::Min(0), Constraint::Length(26)].as_ref())
+				.split(panes[0]);
+			f.render_widget(heatmap_view(app.layout(), &stats, theme), keyboard[0]);
+			f.render_widget(fingers_view(&stats, theme), keyboard[1]);
+		} else {
+			let chart = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints([Constraint::Min(0), Constraint::Length(5)].as_ref())
+				.split(panes[0]);
+			let acc_plot: Vec<(f64, f64)> = result
+				.acc_plot
+				.iter()
+				.map(|(t, acc)| (*t, acc / 100.0 * result.wpm_max))
+				.collect();
+			f.render_widget(
+				chart_view(&result, &acc_plot, app.elapsed_time(), theme),
+				chart[0],
+			);
+			f.render_widget(acc_axis_view(chart[1].height, theme), chart[1]);
+			let tooltip = app
+				.hover()
+				.and_then(|hover| chart_tooltip(&result, app.elapsed_time(), chart[0], hover));
+			if let Some((area, tooltip)) = tooltip {
+				f.render_widget(Clear, area);
+				f.render_widget(tooltip, area);
+			}
+		}
+		f.render_widget(analysis_view(&analysis, theme), details[0]);
+		let line_speeds: Vec<(String, u64)> = analysis
+			.lines
+			.iter()
+			.map(|l| (l.line_no.to_string(), l.wpm as u64))
+			.collect();
+		let line_speeds: Vec<(&str, u64)> = line_speeds
+			.iter()
+			.map(|(label, wpm)| (label.as_str(), *wpm))
+			.collect();
+		f.render_widget(line_speed_view(&line_speeds, theme), details[1]);
 		f.render_widget(
-			chart_view(app, &result.wpm_plot, &result.acc_plot, &theme),
-			chunks[1],
+			help_view(theme, file, app.truncated(), picking, true),
+			chunks[3],
 		);
-		f.render_widget(help_view(&theme, file), chunks[2]);
+		help_hotspots(chunks[3], theme, picking, true)
 	} else if app.typing.is_before_start() {
 		let chunks = Layout::default()
 			.direction(Direction::Vertical)
@@ -74,17 +218,24 @@ pub fn view<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, file: PathBu
 				.as_ref(),
 			)
 			.split(f.size());
-		f.render_widget(time_view(app, theme), chunks[0]);
+		let header = Layout::default()
+			.direction(Direction::Horizontal)
+			.constraints([Constraint::Percentage(40), Constraint::Percentage(60)].as_ref())
+			.split(chunks[0]);
+		f.render_widget(time_view(app, theme), header[0]);
+		if let Some(daily) = app.daily() {
+			f.render_widget(goals_view(daily, theme), header[1]);
+		}
+		text_view(f, app, theme, chunks[1]);
 		f.render_widget(
-			lines(
-				app.typing.display_lines(),
-				app.typing.current_line_index(),
-				app.typing.is_error(),
-				theme,
-			),
-			chunks[1],
+			help_view(theme, file, app.truncated(), picking, false),
+			chunks[2],
 		);
-		f.render_widget(help_view(&theme, file), chunks[2]);
+		[
+			time_hotspots(app, header[0]),
+			help_hotspots(chunks[2], theme, picking, false),
+		]
+		.concat()
 	} else {
 		let chunks = Layout::default()
 			.direction(Direction::Vertical)
@@ -97,42 +248,46 @@ pub fn view<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, file: PathBu
 				.as_ref(),
 			)
 			.split(f.size());
-		f.render_widget(remaining_time_view(&app.typing, theme), chunks[0]);
-		f.render_widget(
-			lines(
-				app.typing.display_lines(),
-				app.typing.current_line_index(),
-				app.typing.is_error(),
-				theme,
-			),
-			chunks[1],
-		);
+		f.render_widget(remaining_time_view(app, theme), chunks[0]);
+		text_view(f, app, theme, chunks[1]);
 		f.render_widget(result_view(&app.typing, Borders::TOP, theme), chunks[2]);
+		Vec::new()
 	}
 }
 
 pub fn chart_view<'a>(
-	app: &App,
-	wpm_dataset: &'a Vec<(f64, f64)>,
-	acc_dataset: &'a Vec<(f64, f64)>,
+	result: &'a TypingResult,
+	acc_dataset: &'a [(f64, f64)],
+	elapsed_time: Duration,
 	theme: &Theme,
 ) -> Chart<'a> {
-	let elapsed_time = app.elapsed_time();
-	let result = app.result();
+	let end = chart_end(result, elapsed_time);
 
 	Chart::new(vec![
+		Dataset::default()
+			.name("burst")
+			.marker(symbols::Marker::Braille)
+			.graph_type(GraphType::Line)
+			.style(Style::default().bg(theme.bg()).fg(Color::Cyan))
+			.data(&result.burst_plot),
 		Dataset::default()
 			.name("wpm")
-			.marker(symbols::Marker::Dot)
+			.marker(symbols::Marker::Braille)
 			.graph_type(GraphType::Line)
 			.style(Style::default().bg(theme.bg()).fg(Color::Yellow))
-			.data(&wpm_dataset),
+			.data(&result.wpm_plot),
 		Dataset::default()
 			.name("acc")
 			.marker(symbols::Marker::Dot)
 			.graph_type(GraphType::Line)
 			.style(Style::default().bg(theme.bg()).fg(Color::DarkGray))
-			.data(&acc_dataset),
+			.data(acc_dataset),
+		Dataset::default()
+			.name("typo")
+			.marker(symbols::Marker::Block)
+			.graph_type(GraphType::Scatter)
+			.style(Style::default().bg(theme.bg()).fg(Color::Red))
+			.data(&result.error_plot),
 	])
 	.style(Style::default().bg(theme.bg()).fg(theme.fg()))
 	.block(Block::default().style(Style::default().bg(theme.bg()).fg(theme.fg())))
@@ -142,15 +297,15 @@ pub fn chart_view<'a>(
 			.labels(vec![
 				Span::styled("0", Style::default().fg(Color::DarkGray)),
 				Span::styled(
-					(elapsed_time.as_secs() / 2).to_string(),
+					format!("{:.0}", end / 2.0),
 					Style::default().fg(Color::DarkGray),
 				),
 				Span::styled(
-					elapsed_time.as_secs().to_string(),
+					format!("{:.0}", end),
 					Style::default().bg(theme.bg()).fg(theme.fg()),
 				),
 			])
-			.bounds([0.0, elapsed_time.as_secs_f64()]),
+			.bounds([0.0, end]),
 	)
 	.y_axis(
 		Axis::default()
@@ -170,11 +325,349 @@ pub fn chart_view<'a>(
 	)
 }
 
-fn help_view<'a>(theme: &Theme, path: PathBuf) -> Paragraph<'a> {
-	let file_path = Spans::from(Span::styled(
-		path.into_os_string().into_string().unwrap(),
+/// The last second on the x axis of `chart_view`.
+fn chart_end(result: &TypingResult, elapsed_time: Duration) -> f64 {
+	result
+		.wpm_plot
+		.last()
+		.map(|(t, _)| *t)
+		.unwrap_or(0.0)
+		.max(elapsed_time.as_secs_f64())
+}
+
+/// A one line box next to the mouse with the wpm of the sample nearest to it,
+/// when it hovers the plot of a `chart_view` drawn in `area`.
+fn chart_tooltip<'a>(
+	result: &TypingResult,
+	elapsed_time: Duration,
+	area: Rect,
+	(column, row): (u16, u16),
+) -> Option<(Rect, Paragraph<'a>)> {
+	// Mirrors the chart's own layout: y labels and the y axis on the left, x labels
+	// and the x axis on the bottom two rows.
+	let labels = [
+		(result.wpm_max / 2.0).floor().to_string(),
+		result.wpm_max.to_string(),
+	];
+	let label_width = labels.iter().map(|l| l.len()).max().unwrap_or(1) as u16;
+	let left = area.x + label_width.min(area.width / 3) + 1;
+	let bottom = area.bottom().saturating_sub(2);
+	if column < left || column >= area.right() || row < area.y || row >= bottom {
+		return None;
+	}
+
+	let width = (area.right() - left).saturating_sub(1).max(1) as f64;
+	let at = (column - left) as f64 / width * chart_end(result, elapsed_time);
+	let nearest = |plot: &[(f64, f64)]| {
+		plot.iter()
+			.min_by(|a, b| (a.0 - at).abs().total_cmp(&(b.0 - at).abs()))
+			.cloned()
+	};
+	let (second, wpm) = nearest(&result.wpm_plot)?;
+	let burst = nearest(&result.burst_plot)
+		.filter(|(t, _)| *t == second)
+		.map(|(_, b)| format!(", burst {:.0}", b))
+		.unwrap_or_default();
+	let text = format!(" {:.0}s: {:.0} wpm{} ", second, wpm, burst);
+	let x = if column + 1 + text.len() as u16 <= area.right() {
+		column + 1
+	} else {
+		area.right().saturating_sub(text.len() as u16).max(area.x)
+	};
+	Some((
+		Rect::new(x, row, text.len() as u16, 1).intersection(area),
+		Paragraph::new(Span::styled(
+			text,
+			Style::default().bg(Color::DarkGray).fg(Color::White),
+		)),
+	))
+}
+
+/// Accuracy scale drawn to the right of `chart_view`, whose acc series is scaled to the wpm axis.
+/// The last two rows of the chart belong to the x axis.
+fn acc_axis_view<'a>(height: u16, theme: &Theme) -> Paragraph<'a> {
+	let bottom = height.saturating_sub(3) as usize;
+	let text: Vec<Spans> = (0..=bottom)
+		.map(|row| {
+			let label = if row == 0 {
+				"100%"
+			} else if row == bottom {
+				"0%"
+			} else if row == bottom / 2 {
+				"50%"
+			} else {
+				""
+			};
+			Spans::from(Span::styled(
+				format!("\u{2502}{}", label),
+				Style::default().bg(theme.bg()).fg(Color::DarkGray),
+			))
+		})
+		.collect();
+	Paragraph::new(text).style(Style::default().bg(theme.bg()).fg(theme.fg()))
+}
+
+fn heat_style(stat: KeyStat) -> Style {
+	if stat.hits == 0 {
+		Style::default().fg(Color::DarkGray)
+	} else if stat.misses == 0 {
+		Style::default().bg(Color::Green).fg(Color::Black)
+	} else if stat.error_rate() < HEAT_WARN_RATE {
+		Style::default().bg(Color::Yellow).fg(Color::Black)
+	} else {
+		Style::default().bg(Color::Red).fg(Color::White)
+	}
+}
+
+fn heatmap_view<'a>(layout: &KeyboardLayout, stats: &KeyStats, theme: &Theme) -> Paragraph<'a> {
+	let mut text: Vec<Spans> = layout
+		.rows
+		.iter()
+		.enumerate()
+		.map(|(r, keys)| {
+			let indent = KEY_ROW_OFFSETS.get(r).cloned().unwrap_or(0);
+			let mut spans = vec![Span::raw(" ".repeat(indent))];
+			for (c, key) in keys.iter().enumerate() {
+				spans.push(Span::styled(
+					format!(" {} ", key.normal),
+					heat_style(stats.key(r, c)),
+				));
+				spans.push(Span::raw(" "));
+			}
+			Spans::from(spans)
+		})
+		.collect();
+	let thumbs = stats
+		.fingers
+		.get(&Finger::Thumb)
+		.cloned()
+		.unwrap_or_default();
+	text.push(Spans::from(vec![
+		Span::raw(" ".repeat(KEY_ROW_OFFSETS[SHIFT_ROW] + 2 * KEY_WIDTH)),
+		Span::styled(format!("{:^23}", "space"), heat_style(thumbs)),
+	]));
+	Paragraph::new(text)
+		.style(Style::default().bg(theme.bg()).fg(theme.fg()))
+		.block(
+			Block::default()
+				.title(Span::styled(
+					format!(
+						"{}: green no typos, yellow under 5%, red 5% or more",
+						layout.name
+					),
+					Style::default().fg(Color::DarkGray),
+				))
+				.style(Style::default().bg(theme.bg()).fg(theme.fg())),
+		)
+}
+
+fn fingers_view<'a>(stats: &KeyStats, theme: &Theme) -> Paragraph<'a> {
+	let mut text = vec![Spans::from(Span::styled(
+		format!("{:<9}{:>6}{:>7}", "finger", "load", "typos"),
 		Style::default().bg(theme.bg()).fg(Color::DarkGray),
-	));
+	))];
+	text.extend(stats.fingers.iter().map(|(finger, stat)| {
+		Spans::from(vec![
+			Span::styled(
+				format!("{:<9}", finger.label()),
+				Style::default().bg(theme.bg()).fg(theme.fg()),
+			),
+			Span::styled(
+				format!("{:>5.0}%", stats.load(*finger) * 100.0),
+				Style::default().bg(theme.bg()).fg(Color::Yellow),
+			),
+			Span::styled(
+				format!("{:>6.1}%", stat.error_rate() * 100.0),
+				heat_style(*stat).bg(theme.bg()),
+			),
+		])
+	}));
+	Paragraph::new(text)
+		.style(Style::default().bg(theme.bg()).fg(theme.fg()))
+		.block(
+			Block::default()
+				.borders(Borders::LEFT)
+				.style(Style::default().bg(theme.bg()).fg(theme.fg())),
+		)
+}
+
+/// The practiced layout drawn as keys, with the next key (and Shift) highlighted
+/// and a mistyped key flashed red.
+pub struct Keyboard<'a> {
+	layout: &'a KeyboardLayout,
+	next: Option<char>,
+	flash: Option<char>,
+	fg: Color,
+	bg: Color,
+}
+
+impl<'a> Keyboard<'a> {
+	pub fn new(layout: &'a KeyboardLayout, theme: &Theme) -> Self {
+		Keyboard {
+			layout,
+			next: None,
+			flash: None,
+			fg: theme.fg(),
+			bg: theme.bg(),
+		}
+	}
+
+	pub fn next(mut self, next: Option<char>) -> Self {
+		self.next = next;
+		self
+	}
+
+	pub fn flash(mut self, flash: Option<char>) -> Self {
+		self.flash = flash;
+		self
+	}
+
+	fn key_style(&self, hit: bool, missed: bool) -> Style {
+		if missed {
+			Style::default().bg(Color::Red).fg(Color::White)
+		} else if hit {
+			Style::default()
+				.bg(Color::Green)
+				.fg(Color::White)
+				.add_modifier(Modifier::BOLD)
+		} else {
+			Style::default().bg(self.bg).fg(self.fg)
+		}
+	}
+}
+
+impl<'a> Widget for Keyboard<'a> {
+	fn render(self, area: Rect, buf: &mut Buffer) {
+		let next = self.next.and_then(|c| self.layout.position(c));
+		let flash = self.flash.and_then(|c| self.layout.position(c));
+		// Shift is pressed with the hand that doesn't type the key.
+		let shift = next
+			.filter(|(_, _, shifted)| *shifted)
+			.and_then(|(r, c, _)| self.layout.key(r, c).map(|k| k.finger <= Finger::LeftIndex));
+
+		let width = KEY_ROW_OFFSETS
+			.iter()
+			.zip(self.layout.rows.iter())
+			.map(|(offset, keys)| (offset + keys.len() * KEY_WIDTH) as u16)
+			.max()
+			.unwrap_or(0)
+			+ SHIFT_LABEL.len() as u16;
+		let left = area.left() + area.width.saturating_sub(width) / 2;
+		let mut put = |x: usize, y: usize, text: &str, style: Style| {
+			let (x, y) = (left + x as u16, area.top() + y as u16);
+			if y < area.bottom() && x < area.right() {
+				buf.set_stringn(x, y, text, (area.right() - x) as usize, style);
+			}
+		};
+
+		for (r, keys) in self.layout.rows.iter().enumerate() {
+			let offset = KEY_ROW_OFFSETS.get(r).cloned().unwrap_or(0);
+			for (c, key) in keys.iter().enumerate() {
+				let at =
+					|p: Option<(usize, usize, bool)>| p.map(|(pr, pc, _)| (pr, pc)) == Some((r, c));
+				put(
+					offset + c * KEY_WIDTH,
+					r,
+					&format!(" {} ", key.normal),
+					self.key_style(at(next), at(flash)),
+				);
+			}
+			if r == SHIFT_ROW {
+				put(
+					0,
+					r,
+					SHIFT_LABEL,
+					self.key_style(shift == Some(false), false),
+				);
+				put(
+					offset + keys.len() * KEY_WIDTH,
+					r,
+					SHIFT_LABEL,
+					self.key_style(shift == Some(true), false),
+				);
+			}
+		}
+		put(
+			KEY_ROW_OFFSETS[SHIFT_ROW] + 2 * KEY_WIDTH,
+			self.layout.rows.len(),
+			&format!("{:^23}", "space"),
+			self.key_style(self.next == Some(' '), self.flash == Some(' ')),
+		);
+	}
+}
+
+fn analysis_view<'a>(analysis: &Analysis, theme: &Theme) -> Paragraph<'a> {
+	let heading = |text: &'a str| {
+		Spans::from(Span::styled(
+			text,
+			Style::default()
+				.bg(theme.bg())
+				.fg(Color::Gray)
+				.add_modifier(Modifier::BOLD),
+		))
+	};
+	let mut text = vec![heading("slowest words")];
+	text.extend(analysis.slowest_words.iter().map(|w| {
+		Spans::from(vec![
+			Span::styled(
+				format!("{:>4} wpm ", w.wpm),
+				Style::default().bg(theme.bg()).fg(Color::Yellow),
+			),
+			Span::styled(
+				w.word.clone(),
+				Style::default().bg(theme.bg()).fg(theme.fg()),
+			),
+			Span::styled(
+				format!("  line {}", w.line_no),
+				Style::default().bg(theme.bg()).fg(Color::DarkGray),
+			),
+		])
+	}));
+	text.push(Spans::from(""));
+	text.push(heading("longest pauses"));
+	text.extend(analysis.longest_pauses.iter().map(|p| {
+		Spans::from(vec![
+			Span::styled(
+				format!("{:>5.1}s ", p.millis as f64 / 1000.0),
+				Style::default().bg(theme.bg()).fg(Color::Red),
+			),
+			Span::styled(
+				p.context.clone(),
+				Style::default().bg(theme.bg()).fg(theme.fg()),
+			),
+			Span::styled(
+				format!("  line {}", p.line_no),
+				Style::default().bg(theme.bg()).fg(Color::DarkGray),
+			),
+		])
+	}));
+	Paragraph::new(text)
+		.style(Style::default().bg(theme.bg()).fg(theme.fg()))
+		.block(
+			Block::default()
+				.borders(Borders::LEFT)
+				.style(Style::default().bg(theme.bg()).fg(theme.fg())),
+		)
+		.alignment(Alignment::Left)
+}
+
+fn line_speed_view<'a>(line_speeds: &'a [(&'a str, u64)], theme: &Theme) -> BarChart<'a> {
+	BarChart::default()
+		.block(
+			Block::default()
+				.title("wpm per line")
+				.borders(Borders::LEFT | Borders::TOP)
+				.style(Style::default().bg(theme.bg()).fg(Color::DarkGray)),
+		)
+		.data(line_speeds)
+		.bar_width(3)
+		.bar_gap(1)
+		.bar_style(Style::default().fg(Color::Cyan))
+		.value_style(Style::default().fg(Color::Black).bg(Color::Cyan))
+		.label_style(Style::default().fg(Color::DarkGray))
+}
+
+fn help_spans<'a>(theme: &Theme, picking: bool, finished: bool) -> Spans<'a> {
 	let help = Spans::from(vec![
 		Span::styled(
 			"r",
@@ -211,8 +704,100 @@ fn help_view<'a>(theme: &Theme, path: PathBuf) -> Paragraph<'a> {
 			" to select a time",
 			Style::default().bg(theme.bg()).fg(Color::DarkGray),
 		),
+		Span::styled(", ", Style::default().bg(theme.bg()).fg(Color::DarkGray)),
+		Span::styled(
+			"ctrl-t",
+			Style::default()
+				.bg(theme.bg())
+				.fg(Color::Green)
+				.add_modifier(Modifier::BOLD),
+		),
+		Span::styled(
+			" to enter one",
+			Style::default().bg(theme.bg()).fg(Color::DarkGray),
+		),
 	]);
-	Paragraph::new(vec![help, file_path])
+	let help = if finished {
+		let mut spans = help.0;
+		spans.extend(vec![
+			Span::styled(", ", Style::default().bg(theme.bg()).fg(Color::DarkGray)),
+			Span::styled(
+				"k",
+				Style::default()
+					.bg(theme.bg())
+					.fg(Color::Green)
+					.add_modifier(Modifier::BOLD),
+			),
+			Span::styled(
+				" to toggle keys",
+				Style::default().bg(theme.bg()).fg(Color::DarkGray),
+			),
+		]);
+		Spans::from(spans)
+	} else {
+		let mut spans = help.0;
+		spans.extend(vec![
+			Span::styled(", ", Style::default().bg(theme.bg()).fg(Color::DarkGray)),
+			Span::styled(
+				"tab",
+				Style::default()
+					.bg(theme.bg())
+					.fg(Color::Green)
+					.add_modifier(Modifier::BOLD),
+			),
+			Span::styled(
+				" to toggle editor mode",
+				Style::default().bg(theme.bg()).fg(Color::DarkGray),
+			),
+		]);
+		Spans::from(spans)
+	};
+	if picking {
+		let mut spans = help.0;
+		spans.extend(vec![
+			Span::styled(", ", Style::default().bg(theme.bg()).fg(Color::DarkGray)),
+			Span::styled(
+				"n",
+				Style::default()
+					.bg(theme.bg())
+					.fg(Color::Cyan)
+					.add_modifier(Modifier::BOLD),
+			),
+			Span::styled(
+				" to pick another file",
+				Style::default().bg(theme.bg()).fg(Color::DarkGray),
+			),
+		]);
+		Spans::from(spans)
+	} else {
+		help
+	}
+}
+
+/// The file being typed, and a warning when only its start was read.
+fn file_spans<'a>(theme: &Theme, path: PathBuf, truncated: bool) -> Spans<'a> {
+	let mut spans = vec![Span::styled(
+		path.into_os_string().into_string().unwrap(),
+		Style::default().bg(theme.bg()).fg(Color::DarkGray),
+	)];
+	if truncated {
+		spans.push(Span::styled(
+			" (only the start was read, see --window and --max-bytes)",
+			Style::default().bg(theme.bg()).fg(Color::Yellow),
+		));
+	}
+	Spans::from(spans)
+}
+
+fn help_view<'a>(
+	theme: &Theme,
+	path: PathBuf,
+	truncated: bool,
+	picking: bool,
+	finished: bool,
+) -> Paragraph<'a> {
+	let file_path = file_spans(theme, path, truncated);
+	Paragraph::new(vec![help_spans(theme, picking, finished), file_path])
 		.style(Style::default().bg(theme.bg()).fg(theme.fg()))
 		.block(
 			Block::default()
@@ -222,9 +807,639 @@ fn help_view<'a>(theme: &Theme, path: PathBuf) -> Paragraph<'a> {
 		.alignment(Alignment::Left)
 }
 
-fn remaining_time_view<'a>(typing: &Typing, theme: &Theme) -> Paragraph<'a> {
+/// Actions under the clickable parts of `help_view`: a key and the label after it.
+fn help_hotspots(area: Rect, theme: &Theme, picking: bool, finished: bool) -> Hotspots {
+	let spans = help_spans(theme, picking, finished).0;
+	// The help line sits under the top border.
+	let y = area.y + 1;
+	let mut x = area.x;
+	let mut hotspots = Vec::new();
+	for (i, span) in spans.iter().enumerate() {
+		let click = match span.content.as_ref() {
+			"r" if finished => Some(Click::Restart),
+			"q" => Some(Click::Quit),
+			"n" if picking => Some(Click::Next),
+			_ => None,
+		};
+		if let Some(click) = click {
+			let width = span.width() + spans.get(i + 1).map(|s| s.width()).unwrap_or(0);
+			hotspots.push((Rect::new(x, y, width as u16, 1).intersection(area), click));
+		}
+		x = x.saturating_add(span.width() as u16);
+	}
+	hotspots
+}
+
+pub fn picker_view<B: Backend>(f: &mut Frame<B>, picker: &Picker, theme: &Theme) {
+	if too_small(f, theme) {
+		return;
+	}
+	let chunks = Layout::default()
+		.direction(Direction::Vertical)
+		.constraints(
+			[
+				Constraint::Length(2),
+				Constraint::Min(0),
+				Constraint::Length(3),
+			]
+			.as_ref(),
+		)
+		.split(f.size());
+	let panes = Layout::default()
+		.direction(Direction::Horizontal)
+		.constraints([Constraint::Percentage(40), Constraint::Percentage(60)].as_ref())
+		.split(chunks[1]);
+
+	let matches = picker.matches();
+	let query = Spans::from(vec![
+		Span::styled("> ", Style::default().bg(theme.bg()).fg(Color::Yellow)),
+		Span::styled(
+			picker.query().to_string(),
+			Style::default()
+				.bg(theme.bg())
+				.fg(theme.fg())
+				.add_modifier(Modifier::BOLD),
+		),
+		Span::styled(
+			format!("  {}/{}  ", matches.len(), picker.total()),
+			Style::default().bg(theme.bg()).fg(Color::DarkGray),
+		),
+		Span::styled(
+			"[random]",
+			Style::default()
+				.bg(theme.bg())
+				.fg(Color::Cyan)
+				.add_modifier(Modifier::BOLD),
+		),
+	]);
+	f.render_widget(
+		Paragraph::new(vec![query])
+			.style(Style::default().bg(theme.bg()).fg(theme.fg()))
+			.block(
+				Block::default()
+					.borders(Borders::BOTTOM)
+					.style(Style::default().bg(theme.bg()).fg(theme.fg())),
+			),
+		chunks[0],
+	);
+
+	let items: Vec<ListItem> = matches
+		.iter()
+		.map(|file| {
+			let stats = picker.stats(file);
+			let detail = match stats.last_wpm {
+				Some(wpm) => format!("  {} wpm x{}", wpm, stats.practiced),
+				None => "  new".to_string(),
+			};
+			ListItem::new(Spans::from(vec![
+				Span::styled(file.display().to_string(), Style::default().fg(theme.fg())),
+				Span::styled(detail, Style::default().fg(Color::DarkGray)),
+			]))
+		})
+		.collect();
+	let mut state = ListState::default();
+	if !matches.is_empty() {
+		state.select(Some(picker.selected_index()));
+	}
+	f.render_stateful_widget(
+		List::new(items)
+			.style(Style::default().bg(theme.bg()).fg(theme.fg()))
+			.block(
+				Block::default()
+					.borders(Borders::RIGHT)
+					.style(Style::default().bg(theme.bg()).fg(theme.fg())),
+			)
+			.highlight_style(
+				Style::default()
+					.fg(Color::Yellow)
+					.add_modifier(Modifier::BOLD),
+			)
+			.highlight_symbol("> "),
+		panes[0],
+		&mut state,
+	);
+
+	f.render_widget(
+		Paragraph::new(picker.preview())
+			.style(Style::default().bg(theme.bg()).fg(Color::Gray))
+			.block(Block::default().style(Style::default().bg(theme.bg()).fg(theme.fg()))),
+		panes[1],
+	);
+
+	let status = match picker.message() {
+		Some(message) => Spans::from(Span::styled(
+			message.clone(),
+			Style::default().bg(theme.bg()).fg(Color::Red),
+		)),
+		None => {
+			let stats = picker
+				.selected()
+				.map(|file| picker.stats(&file))
+				.unwrap_or_default();
+			Spans::from(vec![
+				Span::styled(
+					"practiced: ",
+					Style::default().bg(theme.bg()).fg(Color::DarkGray),
+				),
+				Span::styled(
+					stats.practiced.to_string(),
+					Style::default().bg(theme.bg()).fg(Color::Gray),
+				),
+				Span::styled(
+					" last wpm: ",
+					Style::default().bg(theme.bg()).fg(Color::DarkGray),
+				),
+				Span::styled(
+					stats
+						.last_wpm
+						.map(|w| w.to_string())
+						.unwrap_or_else(|| "-".to_string()),
+					Style::default().bg(theme.bg()).fg(Color::Yellow),
+				),
+			])
+		}
+	};
+	let help = Spans::from(vec![
+		Span::styled(
+			"enter",
+			Style::default()
+				.bg(theme.bg())
+				.fg(Color::Yellow)
+				.add_modifier(Modifier::BOLD),
+		),
+		Span::styled(
+			" to start, ",
+			Style::default().bg(theme.bg()).fg(Color::DarkGray),
+		),
+		Span::styled(
+			"tab",
+			Style::default()
+				.bg(theme.bg())
+				.fg(Color::Cyan)
+				.add_modifier(Modifier::BOLD),
+		),
+		Span::styled(
+			" for a random file, ",
+			Style::default().bg(theme.bg()).fg(Color::DarkGray),
+		),
+		Span::styled(
+			"up, down",
+			Style::default()
+				.bg(theme.bg())
+				.fg(Color::Green)
+				.add_modifier(Modifier::BOLD),
+		),
+		Span::styled(
+			" to select, ",
+			Style::default().bg(theme.bg()).fg(Color::DarkGray),
+		),
+		Span::styled(
+			"esc",
+			Style::default()
+				.bg(theme.bg())
+				.fg(Color::Red)
+				.add_modifier(Modifier::BOLD),
+		),
+		Span::styled(
+			" to quit",
+			Style::default().bg(theme.bg()).fg(Color::DarkGray),
+		),
+	]);
+	f.render_widget(
+		Paragraph::new(vec![status, help])
+			.style(Style::default().bg(theme.bg()).fg(theme.fg()))
+			.block(
+				Block::default()
+					.borders(Borders::TOP)
+					.style(Style::default().bg(theme.bg()).fg(theme.fg())),
+			)
+			.alignment(Alignment::Left),
+		chunks[2],
+	);
+}
+
+pub fn stats_view<B: Backend>(f: &mut Frame<B>, stats: &Stats, period: Period, theme: &Theme) {
+	if too_small(f, theme) {
+		return;
+	}
+	let chunks = Layout::default()
+		.direction(Direction::Vertical)
+		.constraints(
+			[
+				Constraint::Length(2),
+				Constraint::Percentage(50),
+				Constraint::Min(0),
+				Constraint::Length(2),
+			]
+			.as_ref(),
+		)
+		.split(f.size());
+
+	let label =
+		|text: String| Span::styled(text, Style::default().bg(theme.bg()).fg(Color::DarkGray));
+	let value =
+		|text: String| Span::styled(text, Style::default().bg(theme.bg()).fg(Color::Yellow));
+	let summary = Spans::from(vec![
+		label("runs: ".to_string()),
+		value(stats.runs.to_string()),
+		label(" total: ".to_string()),
+		value(format!(
+			"{}h {}m",
+			stats.total_time / 3600,
+			stats.total_time % 3600 / 60
+		)),
+		label(" streak: ".to_string()),
+		value(format!("{} days", stats.current_streak)),
+		label(" longest: ".to_string()),
+		value(format!("{} days", stats.longest_streak)),
+	]);
+	f.render_widget(
+		Paragraph::new(vec![summary])
+			.style(Style::default().bg(theme.bg()).fg(theme.fg()))
+			.block(
+				Block::default()
+					.borders(Borders::BOTTOM)
+					.style(Style::default().bg(theme.bg()).fg(theme.fg())),
+			),
+		chunks[0],
+	);
+
+	let charts = Layout::default()
+		.direction(Direction::Horizontal)
+		.constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+		.split(chunks[1]);
+	// Periods without practice leave a gap rather than pulling the rest closer.
+	let first = stats.periods.first().map(|p| p.start).unwrap_or_default();
+	let wpm: Vec<(f64, f64)> = stats
+		.periods
+		.iter()
+		.map(|p| (period.offset(first, p.start), p.wpm))
+		.collect();
+	let acc: Vec<(f64, f64)> = stats
+		.periods
+		.iter()
+		.map(|p| (period.offset(first, p.start), p.acc))
+		.collect();
+	let dates = (
+		stats
+			.periods
+			.first()
+			.map(|p| p.start.to_string())
+			.unwrap_or_default(),
+		stats
+			.periods
+			.last()
+			.map(|p| p.start.to_string())
+			.unwrap_or_default(),
+	);
+	let wpm_max = wpm.iter().fold(1.0, |max: f64, (_, w)| max.max(*w)).ceil();
+	f.render_widget(
+		history_chart_view(
+			&format!("{} wpm", period.label()),
+			&wpm,
+			wpm_max,
+			&dates,
+			Color::Yellow,
+			theme,
+		),
+		charts[0],
+	);
+	f.render_widget(
+		history_chart_view(
+			&format!("{} acc", period.label()),
+			&acc,
+			100.0,
+			&dates,
+			Color::Gray,
+			theme,
+		),
+		charts[1],
+	);
+
+	let bars = Layout::default()
+		.direction(Direction::Horizontal)
+		.constraints(
+			[
+				Constraint::Percentage(40),
+				Constraint::Percentage(30),
+				Constraint::Percentage(30),
+			]
+			.as_ref(),
+		)
+		.split(chunks[2]);
+	let breakdowns = [
+		("wpm by extension", &stats.by_extension),
+		("wpm by time of day", &stats.by_time_of_day),
+		("wpm by duration", &stats.by_duration),
+	];
+	for (i, (title, data)) in breakdowns.iter().enumerate() {
+		let data: Vec<(&str, u64)> = data.iter().map(|(l, v)| (l.as_str(), *v)).collect();
+		let width = data.iter().map(|(l, _)| l.len()).max().unwrap_or(3).max(3) as u16;
+		f.render_widget(
+			BarChart::default()
+				.block(
+					Block::default()
+						.title(*title)
+						.borders(Borders::TOP)
+						.style(Style::default().bg(theme.bg()).fg(Color::DarkGray)),
+				)
+				.data(&data)
+				.bar_width(width)
+				.bar_gap(1)
+				.bar_style(Style::default().fg(Color::Cyan))
+				.value_style(Style::default().fg(Color::Black).bg(Color::Cyan))
+				.label_style(Style::default().fg(Color::DarkGray)),
+			bars[i],
+		);
+	}
+
+	let help = Spans::from(vec![
+		Span::styled(
+			"w",
+			Style::default()
+				.bg(theme.bg())
+				.fg(Color::Green)
+				.add_modifier(Modifier::BOLD),
+		),
+		Span::styled(
+			" to switch days/weeks, ",
+			Style::default().bg(theme.bg()).fg(Color::DarkGray),
+		),
+		Span::styled(
+			"q",
+			Style::default()
+				.bg(theme.bg())
+				.fg(Color::Red)
+				.add_modifier(Modifier::BOLD),
+		),
+		Span::styled(
+			" to quit",
+			Style::default().bg(theme.bg()).fg(Color::DarkGray),
+		),
+	]);
+	f.render_widget(
+		Paragraph::new(vec![help])
+			.style(Style::default().bg(theme.bg()).fg(theme.fg()))
+			.block(
+				Block::default()
+					.borders(Borders::TOP)
+					.style(Style::default().bg(theme.bg()).fg(theme.fg())),
+			),
+		chunks[3],
+	);
+}
+
+fn history_chart_view<'a>(
+	title: &str,
+	data: &'a [(f64, f64)],
+	y_max: f64,
+	dates: &(String, String),
+	color: Color,
+	theme: &Theme,
+) -> Chart<'a> {
+	Chart::new(vec![Dataset::default()
+		.marker(symbols::Marker::Braille)
+		.graph_type(GraphType::Line)
+		.style(Style::default().bg(theme.bg()).fg(color))
+		.data(data)])
+	.style(Style::default().bg(theme.bg()).fg(theme.fg()))
+	.block(
+		Block::default()
+			.title(Span::styled(
+				title.to_string(),
+				Style::default().fg(Color::Gray),
+			))
+			.style(Style::default().bg(theme.bg()).fg(theme.fg())),
+	)
+	.x_axis(
+		Axis::default()
+			.style(Style::default().bg(theme.bg()).fg(Color::DarkGray))
+			.labels(vec![
+				Span::styled(dates.0.clone(), Style::default().fg(Color::DarkGray)),
+				Span::styled(dates.1.clone(), Style::default().fg(Color::DarkGray)),
+			])
+			.bounds([0.0, data.last().map(|(x, _)| *x).unwrap_or(0.0).max(1.0)]),
+	)
+	.y_axis(
+		Axis::default()
+			.style(Style::default().bg(theme.bg()).fg(theme.fg()))
+			.labels(vec![
+				Span::styled("0", Style::default().fg(Color::DarkGray)),
+				Span::styled(
+					(y_max / 2.0).floor().to_string(),
+					Style::default().fg(Color::DarkGray),
+				),
+				Span::styled(y_max.to_string(), Style::default().fg(Color::DarkGray)),
+			])
+			.bounds([0.0, y_max]),
+	)
+}
+
+pub fn navigation_view<B: Backend>(
+	f: &mut Frame<B>,
+	navigation: &Navigation,
+	theme: &Theme,
+	file: PathBuf,
+	truncated: bool,
+) {
+	if too_small(f, theme) {
+		return;
+	}
+	let chunks = Layout::default()
+		.direction(Direction::Vertical)
+		.constraints(
+			[
+				Constraint::Length(2),
+				Constraint::Min(0),
+				Constraint::Length(3),
+			]
+			.as_ref(),
+		)
+		.split(f.size());
+	let state = navigation.state();
+	f.render_widget(navigation_result_view(navigation, theme), chunks[0]);
+	f.render_widget(motion_lines(state, theme), chunks[1]);
+	f.render_widget(
+		navigation_help_view(theme, file, truncated, navigation.is_finish()),
+		chunks[2],
+	);
+}
+
+fn navigation_result_view<'a>(navigation: &Navigation, theme: &Theme) -> Paragraph<'a> {
+	let state = navigation.state();
+	let label =
+		|text: &'a str| Span::styled(text, Style::default().bg(theme.bg()).fg(Color::DarkGray));
+	let mut spans = if navigation.is_finish() {
+		vec![]
+	} else {
+		vec![Span::styled(
+			format!("{} ", state.remaining_time().as_secs()),
+			Style::default()
+				.bg(theme.bg())
+				.fg(Color::Green)
+				.add_modifier(Modifier::BOLD),
+		)]
+	};
+	spans.extend(vec![
+		label("targets: "),
+		Span::styled(
+			state.reached().to_string(),
+			Style::default().bg(theme.bg()).fg(Color::Yellow),
+		),
+		label(" keys: "),
+		Span::styled(
+			format!("{}/{}", state.keys(), state.optimal()),
+			Style::default().bg(theme.bg()).fg(Color::Gray),
+		),
+		label(" efficiency: "),
+		Span::styled(
+			format!("{}%", state.efficiency()),
+			Style::default().bg(theme.bg()).fg(Color::Gray),
+		),
+	]);
+	if navigation.is_finish() {
+		spans.extend(vec![
+			label(" pace: "),
+			Span::styled(
+				format!("{:.1}s", state.pace()),
+				Style::default().bg(theme.bg()).fg(Color::Gray),
+			),
+		]);
+	} else if !state.pending().is_empty() {
+		spans.extend(vec![
+			label(" pending: "),
+			Span::styled(
+				state.pending(),
+				Style::default()
+					.bg(theme.bg())
+					.fg(Color::Cyan)
+					.add_modifier(Modifier::BOLD),
+			),
+		]);
+	}
+	Paragraph::new(vec![Spans::from(spans)])
+		.alignment(Alignment::Left)
+		.block(
+			Block::default()
+				.borders(Borders::BOTTOM)
+				.style(Style::default().bg(theme.bg()).fg(theme.fg())),
+		)
+}
+
+/// The snippet with the cursor styled like the char being typed and the target in yellow.
+fn motion_lines<'a>(state: &NavigationState, theme: &Theme) -> Paragraph<'a> {
+	let text: Vec<Spans<'a>> = state
+		.text()
+		.iter()
+		.enumerate()
+		.map(|(r, line)| {
+			let mut chars = line.clone();
+			if chars.is_empty() {
+				chars.push(' ');
+			}
+			Spans::from(
+				chars
+					.into_iter()
+					.enumerate()
+					.map(|(c, ch)| {
+						Span::styled(
+							String::from(ch),
+							if (r, c) == state.cursor() {
+								Style::default()
+									.bg(Color::Green)
+									.fg(Color::White)
+									.add_modifier(Modifier::BOLD)
+							} else if (r, c) == state.target() {
+								Style::default()
+									.bg(Color::Yellow)
+									.fg(Color::Black)
+									.add_modifier(Modifier::BOLD)
+							} else {
+								Style::default().bg(theme.bg()).fg(theme.fg())
+							},
+						)
+					})
+					.collect::<Vec<Span>>(),
+			)
+		})
+		.collect();
+	Paragraph::new(text)
+		.block(Block::default().style(Style::default().bg(theme.bg()).fg(theme.fg())))
+		.alignment(Alignment::Left)
+}
+
+fn navigation_help_view<'a>(
+	theme: &Theme,
+	path: PathBuf,
+	truncated: bool,
+	finished: bool,
+) -> Paragraph<'a> {
+	let key = |text: &'a str, color: Color| {
+		Span::styled(
+			text,
+			Style::default()
+				.bg(theme.bg())
+				.fg(color)
+				.add_modifier(Modifier::BOLD),
+		)
+	};
+	let label =
+		|text: &'a str| Span::styled(text, Style::default().bg(theme.bg()).fg(Color::DarkGray));
+	let help = if finished {
+		vec![
+			key("r", Color::Yellow),
+			label(" to restart, "),
+			key("q", Color::Red),
+			label(" to quit"),
+		]
+	} else {
+		vec![
+			key("hjkl w b e f t gg G", Color::Green),
+			label(" to reach the yellow target, "),
+			key("esc", Color::Yellow),
+			label(" to clear a motion, "),
+			key("ctrl-c", Color::Red),
+			label(" to finish"),
+		]
+	};
+	Paragraph::new(vec![Spans::from(help), file_spans(theme, path, truncated)])
+		.style(Style::default().bg(theme.bg()).fg(theme.fg()))
+		.block(
+			Block::default()
+				.borders(Borders::TOP)
+				.style(Style::default().bg(theme.bg()).fg(theme.fg())),
+		)
+		.alignment(Alignment::Left)
+}
+
+fn remaining_time_view<'a>(app: &App, theme: &Theme) -> Paragraph<'a> {
+	let typing = &app.typing;
+	if app.is_paused() {
+		let paused = Spans::from(vec![
+			Span::styled(
+				"paused",
+				Style::default()
+					.bg(theme.bg())
+					.fg(Color::Yellow)
+					.add_modifier(Modifier::BOLD),
+			),
+			Span::styled(
+				" press any key to continue",
+				Style::default().bg(theme.bg()).fg(Color::DarkGray),
+			),
+		]);
+		return Paragraph::new(vec![paused])
+			.style(Style::default().bg(theme.bg()).fg(theme.fg()))
+			.alignment(Alignment::Left);
+	}
 	let time = Spans::from(Span::styled(
-		typing.get_remaining_time().to_string(),
+		if typing.is_warming_up() {
+			"warm-up".to_string()
+		} else if app.tenths() {
+			format!("{:.1}", app.remaining().as_secs_f64())
+		} else {
+			typing.get_remaining_time().to_string()
+		},
 		Style::default()
 			.bg(theme.bg())
 			.fg(Color::Green)
@@ -267,6 +1482,28 @@ fn result_view<'a>(typing: &Typing, border: Borders, theme: &Theme) -> Paragraph
 			Style::default().bg(Theme::bg(&theme)).fg(Color::Red),
 		),
 	]);
+	let result = if typing.is_finish() {
+		let mut spans = result.0;
+		spans.extend(vec![
+			Span::styled(
+				" errors: ",
+				Style::default().bg(theme.bg()).fg(Color::DarkGray),
+			),
+			Span::styled(
+				typing.policy().label(),
+				Style::default().bg(theme.bg()).fg(Color::Gray),
+			),
+		]);
+		if typing.is_editor() {
+			spans.push(Span::styled(
+				" editor mode",
+				Style::default().bg(theme.bg()).fg(Color::Cyan),
+			));
+		}
+		Spans::from(spans)
+	} else {
+		result
+	};
 	Paragraph::new(vec![result])
 		.style(Style::default().bg(Theme::bg(&theme)).fg(Theme::fg(&theme)))
 		.block(
@@ -277,7 +1514,153 @@ fn result_view<'a>(typing: &Typing, border: Borders, theme: &Theme) -> Paragraph
 		.alignment(Alignment::Left)
 }
 
+fn bests_view<'a>(bests: &[Comparison], theme: &Theme) -> Paragraph<'a> {
+	let banner = if bests.iter().any(|b| b.is_record()) {
+		Spans::from(Span::styled(
+			"new personal best!",
+			Style::default()
+				.bg(theme.bg())
+				.fg(Color::Yellow)
+				.add_modifier(Modifier::BOLD),
+		))
+	} else {
+		Spans::from("")
+	};
+	let deltas: Vec<Span> = bests
+		.iter()
+		.flat_map(|b| {
+			let (delta, color) = match b.delta() {
+				Some(d) if b.is_record() => (format!("+{}", d), Color::Yellow),
+				Some(d) if d >= 0 => (format!("+{}", d), Color::Green),
+				Some(d) => (d.to_string(), Color::Red),
+				None => ("-".to_string(), Color::DarkGray),
+			};
+			vec![
+				Span::styled(
+					format!("{} pb: ", b.category.label()),
+					Style::default().bg(theme.bg()).fg(Color::DarkGray),
+				),
+				Span::styled(
+					b.best
+						.map(|w| w.to_string())
+						.unwrap_or_else(|| "-".to_string()),
+					Style::default().bg(theme.bg()).fg(Color::Gray),
+				),
+				Span::styled(" (", Style::default().bg(theme.bg()).fg(Color::DarkGray)),
+				Span::styled(delta, Style::default().bg(theme.bg()).fg(color)),
+				Span::styled(") ", Style::default().bg(theme.bg()).fg(Color::DarkGray)),
+			]
+		})
+		.collect();
+	Paragraph::new(vec![Spans::from(deltas), banner])
+		.style(Style::default().bg(theme.bg()).fg(theme.fg()))
+		.alignment(Alignment::Left)
+}
+
+fn goals_view<'a>(daily: &DailyProgress, theme: &Theme) -> Paragraph<'a> {
+	let goal = |label: &str, done: u64, target: Option<u64>| match target {
+		Some(target) => vec![
+			Span::styled(
+				format!("{} ", label),
+				Style::default().bg(theme.bg()).fg(Color::DarkGray),
+			),
+			Span::styled(
+				format!("{}/{}  ", done, target),
+				Style::default().bg(theme.bg()).fg(if done >= target {
+					Color::Green
+				} else {
+					Color::Yellow
+				}),
+			),
+		],
+		None => vec![],
+	};
+	let goals = daily.goals;
+	let today = daily.today;
+	let spans = [
+		goal("min", today.seconds / 60, goals.minutes_per_day),
+		goal(
+			"runs",
+			today.runs as u64,
+			goals.runs_per_day.map(|r| r as u64),
+		),
+		goal(
+			"wpm",
+			today.best_wpm as u64,
+			goals.target_wpm.map(|w| w as u64),
+		),
+		vec![
+			Span::styled(
+				"streak ",
+				Style::default().bg(theme.bg()).fg(Color::DarkGray),
+			),
+			Span::styled(
+				format!("{} days", daily.streak),
+				Style::default()
+					.bg(theme.bg())
+					.fg(if today.meets(&goals) {
+						Color::Green
+					} else {
+						theme.fg()
+					})
+					.add_modifier(Modifier::BOLD),
+			),
+		],
+	]
+	.concat();
+	Paragraph::new(vec![Spans::from(spans)])
+		.alignment(Alignment::Right)
+		.block(Block::default().style(Style::default().bg(theme.bg()).fg(theme.fg())))
+}
+
 fn time_view<'a>(app: &App, theme: &Theme) -> Paragraph<'a> {
+	if let Some(count) = app.countdown() {
+		let result = Spans::from(Span::styled(
+			format!("starting in {}", count),
+			Style::default()
+				.bg(theme.bg())
+				.fg(Color::Yellow)
+				.add_modifier(Modifier::BOLD),
+		));
+		return Paragraph::new(vec![result])
+			.alignment(Alignment::Left)
+			.block(Block::default().style(Style::default().bg(theme.bg()).fg(theme.fg())));
+	}
+	if let Some(input) = app.time_input() {
+		let valid = parse_duration(input).is_ok();
+		let result = Spans::from(vec![
+			Span::styled(
+				"time: ",
+				Style::default().bg(theme.bg()).fg(Color::DarkGray),
+			),
+			Span::styled(
+				input.clone(),
+				Style::default()
+					.bg(theme.bg())
+					.fg(if valid || input.is_empty() {
+						Color::Yellow
+					} else {
+						Color::Red
+					})
+					.add_modifier(Modifier::BOLD),
+			),
+			Span::styled(
+				"_",
+				Style::default()
+					.bg(theme.bg())
+					.fg(theme.fg())
+					.add_modifier(Modifier::SLOW_BLINK),
+			),
+			Span::styled(
+				"  e.g. 90 or 5m, enter to select, esc to cancel",
+				Style::default().bg(theme.bg()).fg(Color::DarkGray),
+			),
+		]);
+		return Paragraph::new(vec![result])
+			.alignment(Alignment::Left)
+			.block(Block::default().style(Style::default().bg(theme.bg()).fg(theme.fg())));
+	}
+
 	let times: Vec<Span> = app
 		.selectable_time()
 		.iter()
@@ -295,12 +1678,78 @@ fn time_view<'a>(app: &App, theme: &Theme) -> Paragraph<'a> {
 			)
 		})
 		.collect();
-	let result = Spans::from(times);
+	let hint = match app.start_mode() {
+		StartMode::FirstKey => "",
+		StartMode::Enter => " enter to start",
+		StartMode::Countdown => " enter to count down",
+	};
+	let result = Spans::from(
+		[
+			times,
+			vec![Span::styled(
+				hint,
+				Style::default().bg(theme.bg()).fg(Color::DarkGray),
+			)],
+			if app.typing.is_editor() {
+				vec![Span::styled(
+					" editor mode",
+					Style::default().bg(theme.bg()).fg(Color::Cyan),
+				)]
+			} else {
+				vec![]
+			},
+		]
+		.concat(),
+	);
 	Paragraph::new(vec![result])
 		.alignment(Alignment::Left)
 		.block(Block::default().style(Style::default().bg(theme.bg()).fg(theme.fg())))
 }
 
+/// The selectable times of `time_view`, which start at the left of its area.
+fn time_hotspots(app: &App, area: Rect) -> Hotspots {
+	if app.countdown().is_some() || app.time_input().is_some() {
+		return Vec::new();
+	}
+	let mut x = area.x;
+	let mut hotspots = Vec::new();
+	for time in app.selectable_time() {
+		let width = time.as_secs().to_string().len() as u16;
+		hotspots.push((
+			Rect::new(x, area.y, width, 1).intersection(area),
+			Click::Time(time),
+		));
+		x = x.saturating_add(width + 1);
+	}
+	hotspots
+}
+
+/// The lines being typed, with the keyboard panel under them when it's on.
+fn text_view<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, area: Rect) {
+	let text = lines(
+		app.typing.display_lines(),
+		app.typing.current_line_index(),
+		app.typing.is_error(),
+		theme,
+	);
+	if !app.shows_keyboard_panel() {
+		f.render_widget(text, area);
+		return;
+	}
+
+	let chunks = Layout::default()
+		.direction(Direction::Vertical)
+		.constraints([Constraint::Min(0), Constraint::Length(KEYBOARD_HEIGHT)].as_ref())
+		.split(area);
+	f.render_widget(text, chunks[0]);
+	f.render_widget(
+		Keyboard::new(app.layout(), theme)
+			.next(app.typing.current_text())
+			.flash(app.flash()),
+		chunks[1],
+	);
+}
+
 fn lines<'a>(
 	lines: Vec<Line>,
 	current_line_index: usize,
@@ -309,7 +1758,7 @@ fn lines<'a>(
 ) -> Paragraph<'a> {
 	let text: Vec<Spans<'a>> = lines
 		.iter()
-		.map(|l| line(l.clone(), current_line_index, is_typing_error, theme))
+		.map(|l| line(l, current_line_index, is_typing_error, theme))
 		.collect();
 	Paragraph::new(text)
 		.style(Style::default().bg(theme.fg()).fg(theme.bg()))
@@ -318,16 +1767,13 @@ fn lines<'a>(
 }
 
 fn line<'a>(
-	line: Line,
+	line: &Line,
 	current_line_index: usize,
 	is_typing_error: bool,
 	theme: &Theme,
 ) -> Spans<'a> {
 	if line.line_no() - 1 == current_line_index {
-		let entered = Span::styled(
-			line.entered_text().unwrap_or("".to_owned()),
-			Style::default().bg(theme.bg()).fg(Color::Green),
-		);
+		let entered = entered_spans(line, theme);
 		let current = if is_typing_error {
 			Span::styled(
 				line.current_text()
@@ -354,12 +1800,9 @@ fn line<'a>(
 			line.rest_text().unwrap_or("".to_owned()),
 			Style::default().bg(theme.bg()).fg(theme.fg()),
 		);
-		Spans::from(vec![entered, current, rest])
+		Spans::from([entered, vec![current, rest]].concat())
 	} else if line.line_no() - 1 > current_line_index {
-		let entered = Span::styled(
-			line.entered_text().unwrap_or("".to_owned()),
-			Style::default().bg(theme.bg()).fg(Color::Green),
-		);
+		let entered = entered_spans(line, theme);
 		let current = Span::styled(
 			line.current_text()
 				.map(String::from)
@@ -370,12 +1813,9 @@ fn line<'a>(
 			line.rest_text().unwrap_or("".to_owned()),
 			Style::default().bg(theme.bg()).fg(Color::DarkGray),
 		);
-		Spans::from(vec![entered, current, rest])
+		Spans::from([entered, vec![current, rest]].concat())
 	} else {
-		let entered = Span::styled(
-			line.entered_text().unwrap_or("".to_owned()),
-			Style::default().bg(theme.bg()).fg(Color::Green),
-		);
+		let entered = entered_spans(line, theme);
 		let current = Span::styled(
 			line.current_text()
 				.map(String::from)
@@ -386,6 +1826,32 @@ fn line<'a>(
 			line.rest_text().unwrap_or("".to_owned()),
 			Style::default().bg(theme.bg()).fg(Color::DarkGray),
 		);
-		Spans::from(vec![entered, current, rest])
+		Spans::from([entered, vec![current, rest]].concat())
+	}
+}
+
+fn entered_spans<'a>(line: &Line, theme: &Theme) -> Vec<Span<'a>> {
+	let errors = line.error_indices();
+	let entered = line.entered_text().unwrap_or("".to_owned());
+	if errors.is_empty() {
+		return vec![Span::styled(
+			entered,
+			Style::default().bg(theme.bg()).fg(Color::Green),
+		)];
 	}
+
+	entered
+		.chars()
+		.enumerate()
+		.map(|(i, c)| {
+			Span::styled(
+				String::from(c),
+				if errors.contains(&i) {
+					Style::default().bg(theme.bg()).fg(Color::Red)
+				} else {
+					Style::default().bg(theme.bg()).fg(Color::Green)
+				},
+			)
+		})
+		.collect()
 }